@@ -17,7 +17,13 @@ pub use oxinat_core::{
     ClientBuilderAttrs,
     ClientBuilderCore,
     ClientBuilderToken,
+    Error,
+    ErrorResponse,
 };
+#[cfg(all(feature = "blocking", not(feature = "core")))]
+pub use oxinat_core::blocking;
+#[cfg(all(feature = "events-listener", not(feature = "core")))]
+pub use oxinat_core::listener;
 #[cfg(feature = "core")]
 pub use oxinat_core::*;
 #[cfg(feature = "derive")]
@@ -0,0 +1,40 @@
+#![cfg(feature = "blocking")]
+mod common;
+
+use oxinat::{blocking::{self, ClientBuilderBlocking, Retrieve}, models::Project, ClientCore, V2};
+use oxinat_core::{ClientBuilderAttrs, Xnat};
+use wiremock::{matchers::{method, path}, Mock, ResponseTemplate};
+
+#[test]
+fn test_blocking_project_retrieve01() {
+    common::init();
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let server = runtime.block_on(async {
+        let server = common::mock_server().await;
+        Mock::given(method("POST"))
+            .and(path("/data/JSESSIONID"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("SESSION"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/data/projects"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "ResultSet": {"Result": [{"ID": "PROJECT_A"}], "totalRecords": 1}
+            })))
+            .mount(&server)
+            .await;
+        server
+    });
+
+    let client: blocking::Xnat<V2> = Xnat::configure(&server.uri())
+        .with_version(V2)
+        .with_username("username")
+        .with_password("password")
+        .acquire_blocking()
+        .unwrap();
+    assert_eq!(client.get_session_id(), "SESSION", "must acquire a session");
+
+    let found: Vec<Project> = client.get_all().unwrap();
+    assert_eq!(found.len(), 1, "results must contain the project");
+}
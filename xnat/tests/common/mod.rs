@@ -8,6 +8,7 @@ use oxinat::{
     V2
 };
 use oxinat_core::client::timeouts::Timeouts;
+use wiremock::{matchers::method, Mock, MockServer, ResponseTemplate};
 
 #[allow(dead_code)]
 static INIT: Once = Once::new();
@@ -61,6 +62,27 @@ pub async fn request_client() -> Xnat<V2> {
         .unwrap()
 }
 
+/// Starts a local mock XNAT host which allows
+/// all REST methods on every endpoint.
+#[allow(dead_code)]
+pub async fn mock_server() -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("OPTIONS"))
+        .respond_with(ResponseTemplate::new(200)
+            .insert_header("Allow", "GET, HEAD, POST, PUT, DELETE, OPTIONS"))
+        .mount(&server)
+        .await;
+    server
+}
+
+/// Build a client targeting a local mock XNAT
+/// host.
+#[allow(dead_code)]
+pub fn mock_client(server: &MockServer) -> Xnat<V2> {
+    let base_url = server.uri().parse().unwrap();
+    Xnat::new(&base_url, &None, false, &V2)
+}
+
 #[allow(dead_code)]
 pub fn init() {
     INIT.call_once(|| {
@@ -0,0 +1,171 @@
+#![cfg(feature = "events-listener")]
+mod common;
+
+use std::sync::{Arc, Mutex};
+
+use oxinat::listener::{EventKind, EventListener, XnatEvent, WEBHOOK_ACTION_KEY};
+use oxinat::models::EventFilter;
+use wiremock::{matchers::{body_string_contains, method, path}, Mock, ResponseTemplate};
+
+fn delivery(event_type: &str, payload: serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "event-type": format!("org.nrg.xnat.eventservice.events.{event_type}"),
+        "status": "CREATED",
+        "project": "PROJECT_A",
+        "user": "admin",
+        "subscription-id": 4,
+        "timestamp": 1718000000000i64,
+        "payload": payload,
+    })
+}
+
+#[tokio::test]
+async fn test_listener_dispatch01() {
+    common::init();
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let sessions = Arc::new(Mutex::new(Vec::new()));
+    let listener = EventListener::new()
+        .with_path("events")
+        .with_handler({
+            let received = received.clone();
+            move |event: &XnatEvent| received.lock().unwrap().push(event.kind())
+        })
+        .with_handler_for(EventKind::Session, {
+            let sessions = sessions.clone();
+            move |event: &XnatEvent| {
+                if let XnatEvent::Session(event) = event {
+                    sessions.lock().unwrap().push(event.payload.label.clone());
+                }
+            }
+        })
+        .bind("127.0.0.1:0".parse().unwrap())
+        .await
+        .unwrap();
+    assert_eq!(listener.url(), format!("http://{}/events", listener.local_addr()));
+
+    let client = reqwest::Client::new();
+    let deliveries = [
+        delivery("SessionEvent", serde_json::json!({
+            "id": "XNAT_E00001",
+            "label": "SESSION_A",
+            "project-id": "PROJECT_A",
+            "subject-id": "XNAT_S00001",
+            "xsi-type": "xnat:mrSessionData",
+        })),
+        delivery("ScanEvent", serde_json::json!({ "id": "1", "session-id": "XNAT_E00001" })),
+        delivery("WorkflowStatusEvent", serde_json::json!({ "pipeline-name": "dcm2niix", "status": "Complete" })),
+        delivery("SomePluginEvent", serde_json::json!({ "anything": true })),
+    ];
+    for body in &deliveries {
+        let res = client.post(listener.url()).json(body).send().await.unwrap();
+        assert_eq!(res.status(), 200, "must accept deliveries");
+    }
+
+    let res = client.post(listener.url()).body("not json").send().await.unwrap();
+    assert_eq!(res.status(), 400, "must reject malformed deliveries");
+    let res = client.get(listener.url()).send().await.unwrap();
+    assert_eq!(res.status(), 405, "must only accept deliveries by POST");
+    let res = client
+        .post(format!("http://{}/other", listener.local_addr()))
+        .json(&deliveries[0])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(res.status(), 404, "must only accept deliveries on its path");
+
+    listener.shutdown().await;
+    assert_eq!(*received.lock().unwrap(), [
+        EventKind::Session,
+        EventKind::Scan,
+        EventKind::WorkflowStatus,
+        EventKind::Other,
+    ]);
+    assert_eq!(*sessions.lock().unwrap(), [Some("SESSION_A".to_string())]);
+}
+
+#[tokio::test]
+async fn test_listener_subscribe01() {
+    common::init();
+
+    let listener = EventListener::new()
+        .with_public_url("http://worker.internal:8080/events")
+        .bind("127.0.0.1:0".parse().unwrap())
+        .await
+        .unwrap();
+
+    let server = common::mock_server().await;
+    Mock::given(method("POST"))
+        .and(path("/xapi/projects/PROJECT_A/events/subscription"))
+        .and(body_string_contains(WEBHOOK_ACTION_KEY))
+        .and(body_string_contains("\"url\":\"http://worker.internal:8080/events\""))
+        .respond_with(ResponseTemplate::new(201).set_body_json(serde_json::json!({
+            "id": 7,
+            "name": "Notify worker",
+            "active": true,
+            "action-key": WEBHOOK_ACTION_KEY,
+            "attributes": { "url": "http://worker.internal:8080/events" },
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::mock_client(&server);
+    let filter = EventFilter::new("org.nrg.xnat.eventservice.events.SessionEvent", "CREATED");
+    let subscription = listener
+        .subscribe(&client, Some("PROJECT_A"), "Notify worker", filter)
+        .await
+        .unwrap();
+    assert_eq!(subscription.id, Some(7));
+    listener.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_listener_detach01() {
+    common::init();
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let listener = EventListener::new()
+        .with_handler({
+            let received = received.clone();
+            move |event: &XnatEvent| received.lock().unwrap().push(event.kind())
+        })
+        .bind("127.0.0.1:0".parse().unwrap())
+        .await
+        .unwrap();
+    let url = listener.url().to_owned();
+    drop(listener);
+
+    let body = delivery("ProjectEvent", serde_json::json!({ "id": "PROJECT_A" }));
+    let res = reqwest::Client::new().post(url).json(&body).send().await.unwrap();
+    assert_eq!(res.status(), 200, "must keep running once the handle is dropped");
+    assert_eq!(*received.lock().unwrap(), [EventKind::Project]);
+}
+
+#[tokio::test]
+async fn test_listener_body_limit01() {
+    common::init();
+
+    let received = Arc::new(Mutex::new(Vec::new()));
+    let listener = EventListener::new()
+        .with_body_limit(1024)
+        .with_handler({
+            let received = received.clone();
+            move |event: &XnatEvent| received.lock().unwrap().push(event.kind())
+        })
+        .bind("127.0.0.1:0".parse().unwrap())
+        .await
+        .unwrap();
+
+    let client = reqwest::Client::new();
+    let body = delivery("ProjectEvent", serde_json::json!({ "id": "A".repeat(2048) }));
+    let res = client.post(listener.url()).json(&body).send().await.unwrap();
+    assert_eq!(res.status(), 413, "must refuse deliveries over the limit");
+
+    let body = delivery("ProjectEvent", serde_json::json!({ "id": "PROJECT_A" }));
+    let res = client.post(listener.url()).json(&body).send().await.unwrap();
+    assert_eq!(res.status(), 200, "must accept deliveries within the limit");
+
+    listener.shutdown().await;
+    assert_eq!(*received.lock().unwrap(), [EventKind::Project]);
+}
@@ -0,0 +1,34 @@
+//! Kept apart from `client_test`, as these tests
+//! change the environment of the whole process.
+mod common;
+
+use oxinat::*;
+use oxinat_core::{ClientBuilderCore, ClientCore, ClientREST, ProjectUriLegacy};
+use wiremock::{matchers::{method, path}, Mock, ResponseTemplate};
+
+#[tokio::test]
+async fn test_network_no_proxy01() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/data/projects"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("{}"))
+        .mount(&server)
+        .await;
+
+    for name in ["NO_PROXY", "no_proxy", "ALL_PROXY", "all_proxy", "http_proxy"] {
+        std::env::remove_var(name);
+    }
+    std::env::set_var("HTTP_PROXY", "http://proxy.host.invalid:3128");
+
+    let client = Xnat::configure(&server.uri())
+        .with_version(V2)
+        .with_no_proxy("127.0.0.1")
+        .build()
+        .unwrap();
+    let uri = client.version().project_data();
+    let res = client.execute(client.get(&uri).await.unwrap()).await;
+    std::env::remove_var("HTTP_PROXY");
+    assert_eq!(res.unwrap().status(), 200, "must bypass proxies from the environment");
+}
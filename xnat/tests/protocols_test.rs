@@ -1,10 +1,62 @@
 mod common;
 
 use oxinat::{
-    models::{Experiment, Project, Scan, Subject},
-    protocols::Retrieve, ClientToken
+    models::{
+        AliasToken,
+        Assessor,
+        EventFilter,
+        EventSubscription,
+        Experiment,
+        PrearchiveSession,
+        PrearchiveStatus,
+        Project,
+        ProjectGroup,
+        ProjectMember,
+        Resource,
+        Row,
+        Scan,
+        Subject,
+        User,
+    },
+    protocols::{
+        AliasTokens,
+        ArchiveOptions,
+        Create,
+        Download,
+        DownloadOptions,
+        EventSubscriptions,
+        Import,
+        ImportDestination,
+        ImportHandler,
+        ImportLocation,
+        ImportOptions,
+        ImportOverwrite,
+        Membership,
+        MembershipChange,
+        Paging,
+        Prearchive,
+        Retrieve,
+        Share,
+        StatusKind,
+        TrackUpload,
+        Update,
+        Upload,
+        UploadOptions,
+        UploadState,
+        UserAdmin,
+        ZipLayout,
+        ZipSource,
+    }, ClientCore, ClientToken
+};
+use chrono::NaiveDate;
+use futures::StreamExt;
+use oxinat_core::{protocols::Query, Error, ExperimentUri, Result, RetryPolicy};
+use serde::Deserialize;
+use std::time::Duration;
+use wiremock::{
+    matchers::{body_string, body_string_contains, header, method, path, query_param, query_param_is_missing},
+    Mock, MockServer, ResponseTemplate
 };
-use oxinat_core::anyhow;
 
 #[tokio::test]
 #[ignore = "must have a .env file for variables set in env"]
@@ -13,7 +65,7 @@ async fn test_protocols_experiment_retreive01() {
 
     let mut client = common::request_client().await;
 
-    let found: anyhow::Result<Vec<Experiment>> = client.get_all().await;
+    let found: Result<Vec<Experiment>> = client.get_all().await;
     assert!(found.is_ok(), "must be able to retrieve `Experiment`s from host: {found:?}");
     assert!(!found.unwrap().is_empty(), "results must have `some` items");
 
@@ -27,7 +79,7 @@ async fn test_protocols_project_retrieve01() {
 
     let mut client = common::request_client().await;
 
-    let found: anyhow::Result<Vec<Project>> = client.get_all().await;
+    let found: Result<Vec<Project>> = client.get_all().await;
     assert!(found.is_ok(), "must be able to retrieve `Project`s from host: {found:?}");
     assert!(!found.unwrap().is_empty(), "results must have `some` items");
 
@@ -57,7 +109,7 @@ async fn test_protocols_scan_retrieval01() {
 
     let mut client = common::request_client().await;
 
-    let found: anyhow::Result<Vec<Scan>> = client.get_all().await;
+    let found: Result<Vec<Scan>> = client.get_all().await;
     assert!(found.is_err(), "must fail when no specifier provided");
     client.release().await.unwrap();
 }
@@ -71,7 +123,7 @@ async fn test_protocols_scan_retrieval02() {
     let mut model = Scan::default();
     model.experiment.clone_from(&common::env_session_id().into());
 
-    let found: anyhow::Result<Vec<Scan>> = client.get_any_from(&model).await;
+    let found: Result<Vec<Scan>> = client.get_any_from(&model).await;
     assert!(found.is_ok(), "must be able to retrieve `Scan`s from host: {found:?}");
     assert!(!found.unwrap().is_empty(), "results must have `some` items");
 
@@ -88,7 +140,7 @@ async fn test_protocols_scan_retrieval03() {
     model.experiment.clone_from(&common::env_session_id().into());
     model.id.clone_from(&1.to_string().into());
 
-    let found: anyhow::Result<Vec<Scan>> = client.get_any_from(&model).await;
+    let found: Result<Vec<Scan>> = client.get_any_from(&model).await;
     assert!(found.is_ok(), "must be able to retrieve `Scan`s from host: {found:?}");
     assert!(!found.unwrap().is_empty(), "results must have `some` items");
 
@@ -102,7 +154,7 @@ async fn test_protocols_subject_retrieve01() {
 
     let mut client = common::request_client().await;
 
-    let found: anyhow::Result<Vec<Subject>> = client.get_all().await;
+    let found: Result<Vec<Subject>> = client.get_all().await;
     assert!(found.is_ok(), "must be able to retrieve `Subject`s from host: {found:?}");
     assert!(!found.unwrap().is_empty(), "results must have `some` items");
 
@@ -139,3 +191,1693 @@ async fn test_protocols_subject_retrieve03() {
 
     client.release().await.unwrap();
 }
+
+#[tokio::test]
+#[ignore = "must have a .env file for variables set in env"]
+async fn test_protocols_subject_update01() {
+    common::init();
+
+    let mut client = common::request_client().await;
+    let mut model = Subject::default();
+    model.project.clone_from(&common::env_project_id().into());
+
+    let found = client.get_any_from(&model).await.unwrap();
+    let mut model = found.first().cloned().expect("should contain at least one subject");
+    model.project.clone_from(&common::env_project_id().into());
+
+    let updated = client.update_once(model.clone()).await;
+    assert!(updated.is_ok(), "must be able to update `Subject` on host: {updated:?}");
+    assert_eq!(updated.unwrap().label, model.label, "must return the stored subject");
+
+    client.release().await.unwrap();
+}
+
+/// Mount an endpoint which accepts one update
+/// containing `body`, then reports `stored` as
+/// the model it holds.
+async fn mock_update(server: &MockServer, uri: &str, body: &str, stored: serde_json::Value) {
+    Mock::given(method("PUT"))
+        .and(path(uri))
+        .and(body_string_contains(body))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(uri))
+        .and(query_param("format", "json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "items": [{"data_fields": stored}]
+        })))
+        .expect(1)
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn test_protocols_project_update01() {
+    common::init();
+
+    let server = common::mock_server().await;
+    mock_update(&server, "/data/projects/PROJECT_A", "\"description\":\"Updated\"", serde_json::json!({
+        "ID": "PROJECT_A",
+        "description": "Updated",
+    })).await;
+
+    let client = common::mock_client(&server);
+    let mut model = Project::default();
+    model.id.clone_from(&Some("PROJECT_A".into()));
+    model.description.clone_from(&Some("Updated".into()));
+
+    let stored = client.update_once(model).await.unwrap();
+    assert_eq!(stored.id.as_deref(), Some("PROJECT_A"));
+    assert_eq!(stored.description.as_deref(), Some("Updated"), "must return the stored project");
+}
+
+#[tokio::test]
+async fn test_protocols_subject_update02() {
+    common::init();
+
+    // A relabelled subject must be addressed by its
+    // id, as it is when created.
+    let server = common::mock_server().await;
+    mock_update(
+        &server,
+        "/data/projects/PROJECT_A/subjects/XNAT_S00001",
+        "\"label\":\"SUBJECT_B\"",
+        serde_json::json!({"ID": "XNAT_S00001", "label": "SUBJECT_B", "project": "PROJECT_A"}),
+    ).await;
+
+    let client = common::mock_client(&server);
+    let mut model = Subject::default();
+    model.project.clone_from(&Some("PROJECT_A".into()));
+    model.id.clone_from(&Some("XNAT_S00001".into()));
+    model.label.clone_from(&Some("SUBJECT_B".into()));
+
+    let stored = client.update_once(model).await.unwrap();
+    assert_eq!(stored.label.as_deref(), Some("SUBJECT_B"), "must return the stored subject");
+}
+
+#[tokio::test]
+async fn test_protocols_experiment_update01() {
+    common::init();
+
+    let server = common::mock_server().await;
+    mock_update(
+        &server,
+        "/data/projects/PROJECT_A/subjects/XNAT_S00001/experiments/XNAT_E00001",
+        "\"note\":\"Updated\"",
+        serde_json::json!({"ID": "XNAT_E00001", "label": "SESSION_A", "note": "Updated"}),
+    ).await;
+
+    let client = common::mock_client(&server);
+    let mut model = Experiment::default();
+    model.project.clone_from(&Some("PROJECT_A".into()));
+    model.subject_id.clone_from(&Some("XNAT_S00001".into()));
+    model.id.clone_from(&Some("XNAT_E00001".into()));
+    model.note.clone_from(&Some("Updated".into()));
+
+    let stored = client.update_once(model).await.unwrap();
+    assert_eq!(stored.label.as_deref(), Some("SESSION_A"));
+    assert_eq!(stored.note.as_deref(), Some("Updated"), "must return the stored experiment");
+}
+
+#[tokio::test]
+async fn test_protocols_scan_update01() {
+    common::init();
+
+    let server = common::mock_server().await;
+    mock_update(
+        &server,
+        "/data/projects/PROJECT_A/subjects/XNAT_S00001/experiments/XNAT_E00001/scans/1",
+        "\"quality\":\"usable\"",
+        serde_json::json!({"ID": "1", "quality": "usable", "type": "T1"}),
+    ).await;
+
+    let client = common::mock_client(&server);
+    let mut model = Scan::default();
+    model.project.clone_from(&Some("PROJECT_A".into()));
+    model.subject.clone_from(&Some("XNAT_S00001".into()));
+    model.experiment.clone_from(&Some("XNAT_E00001".into()));
+    model.id.clone_from(&Some("1".into()));
+    model.quality.clone_from(&Some("usable".into()));
+
+    let stored = client.update_once(model).await.unwrap();
+    assert_eq!(stored.scan_type.as_deref(), Some("T1"), "must return the stored scan");
+    assert_eq!(stored.experiment.as_deref(), Some("XNAT_E00001"), "must carry over runtime identifiers");
+}
+
+#[tokio::test]
+async fn test_protocols_assessor_update01() {
+    common::init();
+
+    let server = common::mock_server().await;
+    mock_update(
+        &server,
+        "/data/projects/PROJECT_A/subjects/XNAT_S00001/experiments/XNAT_E00001/assessors/XNAT_E00002",
+        "\"label\":\"SESSION_A_QC\"",
+        serde_json::json!({"ID": "XNAT_E00002", "label": "SESSION_A_QC", "session_ID": "XNAT_E00001"}),
+    ).await;
+
+    let client = common::mock_client(&server);
+    let mut model = Assessor::default();
+    model.project.clone_from(&Some("PROJECT_A".into()));
+    model.subject.clone_from(&Some("XNAT_S00001".into()));
+    model.session_id.clone_from(&Some("XNAT_E00001".into()));
+    model.id.clone_from(&Some("XNAT_E00002".into()));
+    model.label.clone_from(&Some("SESSION_A_QC".into()));
+
+    let stored = client.update_once(model).await.unwrap();
+    assert_eq!(stored.label.as_deref(), Some("SESSION_A_QC"), "must return the stored assessor");
+    assert_eq!(stored.subject.as_deref(), Some("XNAT_S00001"), "must carry over runtime identifiers");
+}
+
+#[tokio::test]
+async fn test_protocols_resource_update01() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("PUT"))
+        .and(path("/data/projects/PROJECT_A/resources/NOTES"))
+        .and(body_string_contains("\"content\":\"RAW\""))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/data/projects/PROJECT_A/resources"))
+        .and(query_param("format", "json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "ResultSet": {"Result": [
+                {"label": "DICOM", "xnat_abstractresource_id": "10"},
+                {"label": "NOTES", "xnat_abstractresource_id": "11", "content": "RAW"}
+            ], "totalRecords": "2"}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::mock_client(&server);
+    let model = Resource {
+        project: Some("PROJECT_A".into()),
+        collection: Some("NOTES".into()),
+        content: Some("RAW".into()),
+        ..Default::default()
+    };
+
+    let stored = client.update_once(model).await.unwrap();
+    assert_eq!(stored.id.as_deref(), Some("11"), "must pick the stored resource out of its listing");
+    assert_eq!(stored.collection.as_deref(), Some("NOTES"));
+}
+
+#[tokio::test]
+async fn test_protocols_project_retrieve_retry01() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/data/projects"))
+        .respond_with(ResponseTemplate::new(502))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/data/projects"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "ResultSet": {"Result": [{"ID": "PROJECT_A"}], "totalRecords": 1}
+        })))
+        .mount(&server)
+        .await;
+
+    let mut client = common::mock_client(&server);
+    client.set_retry(&RetryPolicy::default().with_base_delay(std::time::Duration::from_millis(1)));
+
+    let found: Result<Vec<Project>> = client.get_all().await;
+    assert!(found.is_ok(), "must recover from a transient host failure: {found:?}");
+    assert_eq!(found.unwrap().len(), 1, "results must contain the project");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_protocols_project_retrieve_spawn01() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/data/projects"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "ResultSet": {"Result": [{"ID": "PROJECT_A"}], "totalRecords": 1}
+        })))
+        .mount(&server)
+        .await;
+
+    let client = common::mock_client(&server);
+    let handles = (0..4)
+        .map(|_| {
+            let client = client.clone();
+            tokio::spawn(async move { Retrieve::<Project>::get_all(&client).await })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        let found = handle.await.unwrap();
+        assert!(found.is_ok(), "must retrieve from a spawned task: {found:?}");
+    }
+}
+
+#[tokio::test]
+async fn test_protocols_project_create_conflict01() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("PUT"))
+        .and(path("/data/projects/PROJECT_A"))
+        .respond_with(ResponseTemplate::new(409).set_body_raw(
+            "<html><head><title>HTTP Status 409 - Conflict</title></head><body>\
+             <h1>HTTP Status 409 - Conflict</h1><hr/>\
+             <p><b>type</b> Status report</p>\
+             <p><b>message</b> <u>Project &quot;PROJECT_A&quot; already exists</u></p>\
+             </body></html>",
+            "text/html;charset=utf-8"
+        ))
+        .mount(&server)
+        .await;
+
+    let client = common::mock_client(&server);
+    let mut model = Project::default();
+    model.id.clone_from(&Some("PROJECT_A".into()));
+
+    let created = client.create_once(model).await;
+    let Err(Error::Conflict(res)) = created else {
+        panic!("must categorize the failure as a conflict: {created:?}");
+    };
+    assert_eq!(res.method.map(|m| m.to_string()).as_deref(), Some("PUT"), "must keep the request method");
+    assert_eq!(res.url.unwrap().path(), "/data/projects/PROJECT_A", "must keep the request URL");
+    assert_eq!(res.status, 409, "must keep the response status");
+    assert_eq!(
+        res.message.as_deref(),
+        Some("Project \"PROJECT_A\" already exists"),
+        "must decode the message from the error page"
+    );
+}
+
+/// Mount a listing of `total` projects which
+/// honours `offset` and `limit`.
+async fn mock_paged_projects(server: &MockServer, total: u64, limit: u64) {
+    for offset in (0..total).step_by(limit as usize) {
+        let results = (offset..total.min(offset + limit))
+            .map(|i| serde_json::json!({"ID": format!("PROJECT_{i}")}))
+            .collect::<Vec<_>>();
+        Mock::given(method("GET"))
+            .and(path("/data/projects"))
+            .and(query_param("offset", offset.to_string()))
+            .and(query_param("limit", limit.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "ResultSet": {"Result": results, "totalRecords": total.to_string()}
+            })))
+            .expect(1)
+            .mount(server)
+            .await;
+    }
+}
+
+#[tokio::test]
+async fn test_protocols_project_stream01() {
+    common::init();
+
+    let server = common::mock_server().await;
+    mock_paged_projects(&server, 5, 2).await;
+
+    let client = common::mock_client(&server);
+    let model = Project::default();
+    let mut stream = client.stream_from(&model, 2);
+
+    let mut found = vec![];
+    while let Some(project) = stream.next().await {
+        found.push(project.unwrap().id.unwrap());
+        assert_eq!(stream.total_records(), Some(5), "must report the total from the host");
+    }
+    assert_eq!(found.len(), 5, "must yield every project across pages");
+    assert_eq!(found[4], "PROJECT_4", "must yield projects in order");
+    assert_eq!(stream.position(), 5, "must count yielded projects");
+}
+
+#[tokio::test]
+async fn test_protocols_project_stream02() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/data/projects"))
+        .respond_with(ResponseTemplate::new(503))
+        .mount(&server)
+        .await;
+
+    let mut client = common::mock_client(&server);
+    client.set_retry(&RetryPolicy::default().with_max_attempts(1));
+    let model = Project::default();
+    let found = client.stream_from(&model, 2).collect::<Vec<_>>().await;
+    assert_eq!(found.len(), 1, "must stop after the first failed page");
+    assert!(matches!(found[0], Err(Error::Host(_))), "must yield the failure: {found:?}");
+}
+
+#[tokio::test]
+async fn test_protocols_experiment_stream01() {
+    common::init();
+
+    // The host pages the listing, but ignores the
+    // label filter.
+    let server = common::mock_server().await;
+    let labels = ["S0_MR1", "S1_MR2", "S2_MR2", "S3_MR2", "S4_MR1", "S5_MR1", "S6_MR2"];
+    for offset in (0..labels.len()).step_by(2) {
+        let results = labels
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(2)
+            .map(|(i, l)| serde_json::json!({"ID": format!("E{i}"), "label": l}))
+            .collect::<Vec<_>>();
+        Mock::given(method("GET"))
+            .and(path("/data/experiments"))
+            .and(query_param("label", "*_MR1"))
+            .and(query_param("offset", offset.to_string()))
+            .and(query_param("limit", "2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "ResultSet": {"Result": results, "totalRecords": labels.len().to_string()}
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let client = common::mock_client(&server);
+    let query = Query::new(Experiment::default()).with_label("*_MR1");
+    let mut stream = client.stream_query(query, 2);
+
+    let mut found = vec![];
+    while let Some(experiment) = stream.next().await {
+        found.push(experiment.unwrap().id.unwrap());
+        if found.len() < 3 {
+            assert_eq!(stream.total_records(), None, "must not report the unfiltered total");
+        }
+    }
+    assert_eq!(found, ["E0", "E4", "E5"], "must read every page of the host once");
+    assert_eq!(stream.total_records(), Some(3), "must count matches once exhausted");
+}
+
+#[tokio::test]
+async fn test_protocols_project_stream03() {
+    common::init();
+
+    // The host ignores paging, returning every
+    // project each time.
+    let server = common::mock_server().await;
+    let results = (0..5)
+        .map(|i| serde_json::json!({"ID": format!("PROJECT_{i}")}))
+        .collect::<Vec<_>>();
+    Mock::given(method("GET"))
+        .and(path("/data/projects"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "ResultSet": {"Result": results}
+        })))
+        .mount(&server)
+        .await;
+
+    let client = common::mock_client(&server);
+    let model = Project::default();
+    let found = client
+        .stream_from(&model, 2)
+        .map(|p| p.unwrap().id.unwrap())
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(found.len(), 5, "must select each window on the client");
+    assert_eq!(found[4], "PROJECT_4", "must not repeat projects");
+}
+
+#[tokio::test]
+async fn test_protocols_experiment_query01() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/data/experiments"))
+        .and(query_param("xsiType", "xnat:mrSessionData"))
+        .and(query_param("label", "*_MR1"))
+        .and(query_param("date", "01/01/2024-12/31/2024"))
+        .and(query_param("sortBy", "date"))
+        .and(query_param("sortOrder", "DESC"))
+        .and(query_param("limit", "10"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "ResultSet": {"Result": [
+                {"ID": "E1", "label": "S1_MR1", "date": "2024-03-01", "xsiType": "xnat:mrSessionData"},
+                {"ID": "E2", "label": "S2_MR2", "date": "2024-02-01", "xsiType": "xnat:mrSessionData"},
+                {"ID": "E3", "label": "S3_MR1", "date": "2023-12-31", "xsiType": "xnat:mrSessionData"}
+            ], "totalRecords": "3"}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::mock_client(&server);
+    let from = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let to = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+    let query = Query::new(Experiment::default())
+        .with_xsi_type("xnat:mrSessionData")
+        .with_label("*_MR1")
+        .with_date(from..=to)
+        .with_sort_by("date")
+        .with_descending(true)
+        .with_limit(10);
+
+    let found = client.query(&query).await.unwrap();
+    let found = found.results.iter().map(|e| e.id.clone().unwrap()).collect::<Vec<_>>();
+    assert_eq!(found, ["E1"], "must drop records the host did not filter");
+}
+
+#[tokio::test]
+async fn test_protocols_experiment_query02() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/data/experiments/E1"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "items": [{"data_fields": {"ID": "E1", "label": "S1_MR1", "date": "2020-06-01"}}]
+        })))
+        .mount(&server)
+        .await;
+
+    let client = common::mock_client(&server);
+    let mut model = Experiment::default();
+    model.id.clone_from(&Some("E1".into()));
+
+    let matching = client.query(&Query::new(model.clone()).with_label("s1_*")).await.unwrap();
+    assert_eq!(matching.results.len(), 1, "must match labels by wildcard on the client");
+
+    let since = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let outside = client.query(&Query::new(model).with_date(since..)).await.unwrap();
+    assert!(outside.results.is_empty(), "must filter dates on the client");
+}
+
+#[tokio::test]
+async fn test_protocols_experiment_query03() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/data/experiments"))
+        .and(query_param_is_missing("date"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "ResultSet": {"Result": [
+                {"ID": "E1", "label": "S1_MR1", "date": "2024-03-01"},
+                {"ID": "E2", "label": "S2_MR1"},
+                {"ID": "E3", "label": "S3_MR1", "date": "2023-12-31"}
+            ], "totalRecords": "3"}
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::mock_client(&server);
+    let since = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let query = Query::new(Experiment::default()).with_date(since..);
+
+    let found = client.query(&query).await.unwrap();
+    let found = found.results.iter().map(|e| e.id.clone().unwrap()).collect::<Vec<_>>();
+    assert_eq!(found, ["E1"], "must drop undated records the host did not filter");
+}
+
+#[tokio::test]
+async fn test_protocols_experiment_select01() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/data/experiments"))
+        .and(query_param("columns", "ID,label,xnat:mrSessionData/fieldStrength"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "ResultSet": {"Result": [
+                {"ID": "E1", "label": "S1_MR1", "xnat:mrsessiondata/fieldstrength": "3.0"},
+                {"ID": "E2", "label": "S2_MR1", "xnat:mrsessiondata/fieldstrength": ""}
+            ], "totalRecords": "2"}
+        })))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let client = common::mock_client(&server);
+    let query = Query::new(Experiment::default())
+        .with_columns(&["ID", "label", "xnat:mrSessionData/fieldStrength"]);
+
+    let rows = client.select::<Row<Experiment>>(&query).await.unwrap();
+    assert_eq!(rows.total_records, Some(2));
+    assert_eq!(rows.results[0].model.id.as_deref(), Some("E1"));
+    assert_eq!(
+        rows.results[0].column_str("xnat:mrSessionData/fieldStrength"),
+        Some("3.0"),
+        "must capture columns the model does not define"
+    );
+    assert!(!rows.results[0].columns.contains_key("ID"), "must not repeat model fields");
+
+    #[derive(Deserialize)]
+    struct Session {
+        #[serde(rename = "ID")]
+        id: String,
+        #[serde(rename = "xnat:mrsessiondata/fieldstrength")]
+        field_strength: String,
+    }
+
+    let sessions = client.select::<Session>(&query).await.unwrap();
+    let found = sessions
+        .results
+        .iter()
+        .map(|s| (s.id.as_str(), s.field_strength.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(found, [("E1", "3.0"), ("E2", "")]);
+}
+
+#[tokio::test]
+async fn test_protocols_resource_upload01() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("PUT"))
+        .and(path("/data/experiments/E1/resources/NIFTI/files/brain.nii"))
+        .and(query_param("inbody", "true"))
+        .and(query_param("format", "NIFTI"))
+        .and(query_param("content", "T1_DERIVED"))
+        .and(query_param("tags", "derived,t1"))
+        .and(query_param("overwrite", "true"))
+        .and(body_string("NIFTI-BYTES"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let file = std::env::temp_dir().join("oxinat_upload01_brain.nii");
+    std::fs::write(&file, "NIFTI-BYTES").unwrap();
+
+    let client = common::mock_client(&server);
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    let options = UploadOptions::default()
+        .with_format("NIFTI")
+        .with_content("T1_DERIVED")
+        .with_tags(&["derived", "t1"])
+        .with_overwrite(true)
+        .with_progress_channel(sender);
+    let experiment = client.version().experiment_data().with_experiment("E1");
+    let uri = experiment.resources().with_resource("NIFTI").with_file("brain.nii");
+
+    client.upload_reader(&uri, tokio::fs::File::open(&file).await.unwrap(), Some(11), &options)
+        .await
+        .unwrap();
+    std::fs::remove_file(&file).unwrap();
+
+    let mut last = None;
+    while let Ok(progress) = receiver.try_recv() {
+        last = Some(progress);
+    }
+    let last = last.expect("must report progress");
+    assert_eq!(last.file, "brain.nii");
+    assert_eq!((last.bytes_sent, last.total_bytes), (11, Some(11)));
+}
+
+#[tokio::test]
+async fn test_protocols_resource_upload02() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("POST"))
+        .and(path("/data/experiments/E1/resources/REPORTS/files"))
+        .and(query_param("extract", "false"))
+        .and(body_string_contains("filename=\"oxinat_upload02_a.txt\""))
+        .and(body_string_contains("filename=\"oxinat_upload02_b.txt\""))
+        .and(body_string_contains("REPORT-B"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/data/experiments/E1/resources/MISSING/files"))
+        .respond_with(ResponseTemplate::new(404))
+        .mount(&server)
+        .await;
+
+    let dir = std::env::temp_dir();
+    let files = [dir.join("oxinat_upload02_a.txt"), dir.join("oxinat_upload02_b.txt")];
+    std::fs::write(&files[0], "REPORT-A").unwrap();
+    std::fs::write(&files[1], "REPORT-B").unwrap();
+
+    let client = common::mock_client(&server);
+    let sent = std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0));
+    let options = UploadOptions::default()
+        .with_extract(false)
+        .with_progress({
+            let sent = sent.clone();
+            move |p| sent.store(p.bytes_sent, std::sync::atomic::Ordering::Relaxed)
+        });
+    let experiment = client.version().experiment_data().with_experiment("E1");
+
+    let uri = experiment.resources().with_resource("REPORTS");
+    let uploaded = client.upload_files(&uri, &files, &options).await;
+    assert!(uploaded.is_ok(), "must upload files as multipart: {uploaded:?}");
+    assert_eq!(sent.load(std::sync::atomic::Ordering::Relaxed), 16);
+
+    let uri = experiment.resources().with_resource("MISSING");
+    let missing = client.upload_files(&uri, &files, &options).await;
+    assert!(matches!(missing, Err(Error::NotFound(_))), "must surface error status: {missing:?}");
+
+    files.iter().for_each(|f| std::fs::remove_file(f).unwrap());
+}
+
+#[tokio::test]
+async fn test_protocols_resource_download01() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/data/experiments/E1/resources/NIFTI/files/brain.nii"))
+        .and(header("range", "bytes=6-"))
+        .respond_with(ResponseTemplate::new(206).set_body_string("BYTES"))
+        .expect(2)
+        .mount(&server)
+        .await;
+
+    let dir = std::env::temp_dir().join("oxinat_download01");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let dest = dir.join("brain.nii");
+
+    let client = common::mock_client(&server);
+    let mut model = Resource::default();
+    model.uri.clone_from(&Some("/data/experiments/E1/resources/NIFTI/files/brain.nii".into()));
+    model.digest.clone_from(&Some("2497060d77ea6839d2f9bb885b88167e".into()));
+    let options = DownloadOptions::default().with_verify(true);
+
+    std::fs::write(dir.join("brain.nii.part"), "NIFTI-").unwrap();
+    let written = client.download_to(&model, &dest, &options).await.unwrap();
+    assert_eq!(written, vec![dest.clone()]);
+    assert_eq!(std::fs::read_to_string(&dest).unwrap(), "NIFTI-BYTES", "must resume from partial file");
+
+    std::fs::remove_file(&dest).unwrap();
+    std::fs::write(dir.join("brain.nii.part"), "NIFTI_").unwrap();
+    let corrupt = client.download_to(&model, &dest, &options).await;
+    assert!(matches!(corrupt, Err(Error::Checksum { .. })), "must verify digest: {corrupt:?}");
+    assert!(!dest.exists() && !dir.join("brain.nii.part").exists());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_protocols_resource_download02() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/data/experiments/E1/resources/DICOM/files"))
+        .and(query_param("format", "json"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "ResultSet": {"Result": [
+                {"Name": "1.dcm", "URI": "/data/experiments/E1/resources/11/files/series/1.dcm"},
+                {"Name": "2.dcm", "URI": "/data/experiments/E1/resources/11/files/series/2.dcm"}
+            ]}
+        })))
+        .mount(&server)
+        .await;
+    for name in ["1.dcm", "2.dcm"] {
+        Mock::given(method("GET"))
+            .and(path(format!("/data/experiments/E1/resources/11/files/series/{name}")))
+            .respond_with(ResponseTemplate::new(200).set_body_string(format!("DICOM {name}")))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path("/data/experiments/E1/resources/EVIL/files"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "ResultSet": {"Result": [
+                {"Name": "passwd", "URI": "/data/experiments/E1/resources/12/files/../../passwd"}
+            ]}
+        })))
+        .mount(&server)
+        .await;
+
+    let dir = std::env::temp_dir().join("oxinat_download02");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let client = common::mock_client(&server);
+    let mut model = Resource::default();
+    model.experiment.clone_from(&Some("E1".into()));
+    model.collection.clone_from(&Some("DICOM".into()));
+    let options = DownloadOptions::default().with_parallel(2);
+
+    let mut written = client.download_to(&model, &dir, &options).await.unwrap();
+    written.sort();
+    assert_eq!(written, [dir.join("series/1.dcm"), dir.join("series/2.dcm")]);
+    assert_eq!(std::fs::read_to_string(&written[1]).unwrap(), "DICOM 2.dcm");
+
+    model.collection.clone_from(&Some("EVIL".into()));
+    let escaped = client.download_to(&model, &dir, &options).await;
+    assert!(matches!(escaped, Err(Error::UnsafePath(_))), "must refuse paths outside destination: {escaped:?}");
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// Build a zip archive holding each of `files`,
+/// keyed by their path within the archive.
+fn zip_of(files: &[(&str, &str)]) -> Vec<u8> {
+    use std::io::Write;
+
+    let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Stored);
+    for (name, content) in files {
+        writer.start_file(*name, options).unwrap();
+        writer.write_all(content.as_bytes()).unwrap();
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+#[tokio::test]
+async fn test_protocols_experiment_zip01() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/data/experiments/E1/scans/ALL/files"))
+        .and(query_param("format", "zip"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(zip_of(&[
+            ("E1/scans/1-T1/resources/DICOM/files/a.dcm", "T1"),
+            ("E1/scans/2-T2/resources/DICOM/files/a.dcm", "T2"),
+        ])))
+        .mount(&server)
+        .await;
+
+    let dir = std::env::temp_dir().join("oxinat_zip01");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let client = common::mock_client(&server);
+    let source = ZipSource::experiment("E1");
+
+    let xnat = client.download_extracted(&source, &dir.join("xnat"), &ZipLayout::Xnat).await.unwrap();
+    assert_eq!(xnat, [
+        dir.join("xnat/E1/scans/1-T1/resources/DICOM/files/a.dcm"),
+        dir.join("xnat/E1/scans/2-T2/resources/DICOM/files/a.dcm"),
+    ]);
+    assert!(!dir.join("xnat/.oxinat-download.zip").exists(), "must remove the archive");
+
+    let flat = client.download_extracted(&source, &dir.join("flat"), &ZipLayout::Flat).await.unwrap();
+    assert_eq!(flat, [dir.join("flat/a.dcm"), dir.join("flat/a (1).dcm")]);
+    assert_eq!(std::fs::read_to_string(&flat[1]).unwrap(), "T2");
+
+    let layout = ZipLayout::custom(|p| {
+        let scan = p.components().nth(2)?.as_os_str().to_str()?.split('-').next()?.to_owned();
+        Some(std::path::PathBuf::from("PROJECT_A/SUBJECT_A/E1").join(scan).join(p.file_name()?))
+    });
+    let custom = client.download_extracted(&source, &dir.join("custom"), &layout).await.unwrap();
+    assert_eq!(custom[0], dir.join("custom/PROJECT_A/SUBJECT_A/E1/1/a.dcm"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_protocols_experiment_zip02() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/data/experiments/E1/scans/1,2/resources/NIFTI/files"))
+        .and(query_param("format", "zip"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(zip_of(&[
+            ("E1/scans/1/resources/NIFTI/files/ok.nii", "OK"),
+            ("E1/../../escaped.nii", "EVIL"),
+        ])))
+        .mount(&server)
+        .await;
+
+    let dir = std::env::temp_dir().join("oxinat_zip02");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let client = common::mock_client(&server);
+    let source = ZipSource::scans("E1", &["1", "2"]).with_resources(&["NIFTI"]);
+
+    let archive = dir.join("scans.zip");
+    client.download_zip(&source, &archive).await.unwrap();
+    assert!(archive.exists(), "must write the archive as given");
+
+    let extracted = client.download_extracted(&source, &dir.join("out"), &ZipLayout::Xnat).await;
+    assert!(matches!(extracted, Err(Error::UnsafePath(_))), "must refuse paths outside destination: {extracted:?}");
+    assert!(!dir.join("out/E1").exists(), "must not write anything from an unsafe archive");
+    assert!(!dir.join("escaped.nii").exists());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_protocols_session_import01() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("POST"))
+        .and(path("/data/services/import"))
+        .and(query_param("import-handler", "DICOM-zip"))
+        .and(query_param("PROJECT_ID", "PROJECT_A"))
+        .and(query_param("SUBJECT_ID", "SUBJECT_A"))
+        .and(query_param("EXPT_LABEL", "SESSION_A"))
+        .and(query_param("dest", "/prearchive"))
+        .and(query_param("overwrite", "append"))
+        .and(body_string_contains("filename=\"oxinat_import01.zip\""))
+        .respond_with(ResponseTemplate::new(200)
+            .set_body_string("/data/prearchive/projects/PROJECT_A/20240101_120000123/SESSION_A\r\n"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let archive = std::env::temp_dir().join("oxinat_import01.zip");
+    std::fs::write(&archive, "PK").unwrap();
+
+    let client = common::mock_client(&server);
+    let options = ImportOptions::default()
+        .with_handler(ImportHandler::DicomZip)
+        .with_project("PROJECT_A")
+        .with_subject("SUBJECT_A")
+        .with_experiment("SESSION_A")
+        .with_destination(ImportDestination::Prearchive)
+        .with_overwrite(ImportOverwrite::Append);
+
+    let location = client.import_archive(&archive, &options).await.unwrap();
+    std::fs::remove_file(&archive).unwrap();
+    assert_eq!(location, ImportLocation::Prearchive {
+        project: "PROJECT_A".into(),
+        timestamp: "20240101_120000123".into(),
+        session: "SESSION_A".into(),
+    });
+}
+
+#[tokio::test]
+async fn test_protocols_session_import02() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("POST"))
+        .and(path("/data/services/import"))
+        .and(query_param("inbody", "true"))
+        .and(query_param("import-handler", "gradual-DICOM"))
+        .and(query_param("Direct-Archive", "true"))
+        .and(query_param("auto-archive", "true"))
+        .and(body_string("DICM"))
+        .respond_with(ResponseTemplate::new(200)
+            .set_body_string("/data/archive/projects/PROJECT_A/subjects/SUBJECT_A/experiments/SESSION_A"))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/data/services/import"))
+        .and(query_param("import-handler", "SI"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("Upload complete"))
+        .mount(&server)
+        .await;
+
+    let file = std::env::temp_dir().join("oxinat_import02.dcm");
+    std::fs::write(&file, "DICM").unwrap();
+
+    let client = common::mock_client(&server);
+    let options = ImportOptions::default()
+        .with_handler(ImportHandler::GradualDicom)
+        .with_direct_archive(true)
+        .with_auto_archive(true);
+
+    let location = client.import_file(&file, &options).await.unwrap();
+    assert_eq!(location, ImportLocation::Archive {
+        project: "PROJECT_A".into(),
+        subject: "SUBJECT_A".into(),
+        experiment: "SESSION_A".into(),
+    });
+
+    let options = options.with_handler(ImportHandler::SessionImporter);
+    let unknown = client.import_file(&file, &options).await;
+    assert!(matches!(unknown, Err(Error::Deserialization { .. })), "must report unknown locations: {unknown:?}");
+    std::fs::remove_file(&file).unwrap();
+}
+
+/// A prearchived session as XNAT lists it.
+fn prearchive_row(timestamp: &str, folder: &str, status: &str) -> serde_json::Value {
+    serde_json::json!({
+        "project": "PROJECT_A",
+        "timestamp": timestamp,
+        "folderName": folder,
+        "name": folder,
+        "subject": "SUBJECT_A",
+        "status": status,
+        "uploaded": "2024-01-01 12:00:00.123",
+        "lastmod": "2024-01-01 12:05:00.0",
+        "url": format!("/prearchive/projects/PROJECT_A/{timestamp}/{folder}"),
+    })
+}
+
+#[tokio::test]
+async fn test_protocols_prearchive_session01() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/data/prearchive/projects/PROJECT_A"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "ResultSet": {
+                "Result": [
+                    prearchive_row("20240101_120000123", "SESSION_A", "READY"),
+                    prearchive_row("20240102_120000123", "SESSION_B", "CONFLICT"),
+                    prearchive_row("20240103_120000123", "SESSION_C", "_RECEIVING"),
+                ],
+                "totalRecords": "3"
+            }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/data/prearchive/projects/PROJECT_A/20240102_120000123/SESSION_B/scans"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "ResultSet": {
+                "Result": [
+                    {"ID": "1", "series_description": "T1"},
+                    {"ID": "2", "series_description": "T2"},
+                ]
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let client = common::mock_client(&server);
+    let sessions = client.prearchive_sessions(Some("PROJECT_A")).await.unwrap();
+    let statuses = sessions.iter().map(|s| s.status.to_owned().unwrap()).collect::<Vec<_>>();
+    assert_eq!(statuses, [PrearchiveStatus::Ready, PrearchiveStatus::Conflict, PrearchiveStatus::Unknown]);
+    assert_eq!(sessions[0].last_modified().as_deref(), Some("2024-01-01 12:05:00.0"));
+
+    let session = client.prearchive_session(&sessions[1]).await.unwrap();
+    assert_eq!(session.folder_name.as_deref(), Some("SESSION_B"));
+    assert_eq!(session.scan_count, Some(2));
+
+    let mut missing = sessions[1].clone();
+    missing.timestamp = Some("20240104_120000123".into());
+    let missing = client.prearchive_session(&missing).await;
+    assert!(matches!(missing, Err(Error::NotFound(_))), "must report sessions not in the prearchive: {missing:?}");
+}
+
+#[tokio::test]
+async fn test_protocols_prearchive_session02() {
+    common::init();
+
+    let src = "/prearchive/projects/PROJECT_A/20240101_120000123/SESSION_A";
+    let server = common::mock_server().await;
+    for endpoint in ["rebuild", "delete"] {
+        Mock::given(method("POST"))
+            .and(path(format!("/data/prearchive/{endpoint}")))
+            .and(query_param("src", src))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("POST"))
+        .and(path("/data/prearchive/move"))
+        .and(query_param("src", src))
+        .and(query_param("newProject", "PROJECT_B"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/data/services/archive"))
+        .and(query_param("src", src))
+        .and(query_param("dest", "/archive/projects/PROJECT_A/subjects/SUBJECT_A/experiments/SESSION_A_MR1"))
+        .and(query_param("overwrite", "append"))
+        .respond_with(ResponseTemplate::new(200)
+            .set_body_string("/data/archive/projects/PROJECT_A/subjects/SUBJECT_A/experiments/SESSION_A_MR1"))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let session: PrearchiveSession = serde_json::from_value(
+        prearchive_row("20240101_120000123", "SESSION_A", "READY")).unwrap();
+    assert_eq!(session.src().unwrap(), src);
+
+    let client = common::mock_client(&server);
+    client.rebuild_session(&session).await.unwrap();
+    client.move_session(&session, "PROJECT_B").await.unwrap();
+
+    let options = ArchiveOptions::default()
+        .with_label("SESSION_A_MR1")
+        .with_overwrite(ImportOverwrite::Append);
+    let location = client.archive_session(&session, &options).await.unwrap();
+    assert_eq!(location, ImportLocation::Archive {
+        project: "PROJECT_A".into(),
+        subject: "SUBJECT_A".into(),
+        experiment: "SESSION_A_MR1".into(),
+    });
+    client.delete_session(&session).await.unwrap();
+
+    let mut unnamed = session.clone();
+    unnamed.timestamp = None;
+    let unnamed = client.rebuild_session(&unnamed).await;
+    assert!(matches!(unnamed, Err(Error::IdentifierRequired(_))), "must require the session timestamp: {unnamed:?}");
+}
+
+#[tokio::test]
+async fn test_protocols_upload_status01() {
+    common::init();
+
+    let id = oxinat_core::protocols::new_upload_id();
+    let status_path = format!("/data/status/{id}");
+    let server = common::mock_server().await;
+    Mock::given(method("POST"))
+        .and(path("/data/services/import"))
+        .and(query_param("http-session-listener", id.as_str()))
+        .respond_with(ResponseTemplate::new(200)
+            .set_body_string("/data/prearchive/projects/PROJECT_A/20240101_120000123/SESSION_A"))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(&status_path))
+        .respond_with(ResponseTemplate::new(404))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(&status_path))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "msgs": [[
+                {"status": "PROCESSING", "msg": "Extracting files", "percent": 40},
+            ]]
+        })))
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(&status_path))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "msgs": [[
+                {"status": "PROCESSING", "msg": "Extracting files", "percent": 40},
+                {"status": "COMPLETED", "msg": "/data/prearchive/projects/PROJECT_A/20240101_120000123/SESSION_A"},
+            ]]
+        })))
+        .mount(&server)
+        .await;
+
+    let archive = std::env::temp_dir().join("oxinat_status01.zip");
+    std::fs::write(&archive, "PK").unwrap();
+
+    let client = common::mock_client(&server);
+    let options = ImportOptions::default().with_upload_id(&id);
+    client.import_archive(&archive, &options).await.unwrap();
+    std::fs::remove_file(&archive).unwrap();
+
+    let handle = client.track_upload(&id).with_interval(Duration::from_millis(10));
+    assert!(matches!(handle.status().await, Err(Error::NotFound(_))), "status must not be found before XNAT registers the upload");
+
+    let status = handle.status().await.unwrap();
+    assert_eq!((status.state, status.progress), (UploadState::InProgress, 40));
+
+    let status = handle.wait_until_complete(Duration::from_secs(5)).await.unwrap();
+    assert_eq!((status.state, status.progress), (UploadState::Completed, 100));
+    assert_eq!(status.messages.len(), 2);
+    assert_eq!(status.location(), Some(ImportLocation::Prearchive {
+        project: "PROJECT_A".into(),
+        timestamp: "20240101_120000123".into(),
+        session: "SESSION_A".into(),
+    }));
+}
+
+#[tokio::test]
+async fn test_protocols_upload_status02() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/data/status/FAILED_UPLOAD"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {"status": "WARNING", "msg": "Unrecognized file skipped"},
+            {"status": "FAILED", "msg": "Session is locked"},
+        ])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/data/status/STALLED_UPLOAD"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {"status": "PROCESSING", "msg": "Waiting for files"},
+        ])))
+        .mount(&server)
+        .await;
+
+    let client = common::mock_client(&server);
+    let status = client
+        .track_upload("FAILED_UPLOAD")
+        .wait_until_complete(Duration::from_secs(5))
+        .await
+        .unwrap();
+    assert_eq!(status.state, UploadState::Failed);
+    assert_eq!(status.messages[0].status, StatusKind::Warning);
+    assert_eq!(status.location(), None);
+
+    let stalled = client
+        .track_upload("STALLED_UPLOAD")
+        .with_interval(Duration::from_millis(10))
+        .wait_until_complete(Duration::from_millis(100))
+        .await;
+    assert!(matches!(stalled, Err(Error::Timeout(_))), "must give up on uploads which do not complete: {stalled:?}");
+}
+
+#[tokio::test]
+async fn test_protocols_subject_share01() {
+    common::init();
+
+    let subject_path = "/data/projects/PROJECT_A/subjects/SUBJECT_A/projects";
+    let server = common::mock_server().await;
+    Mock::given(method("PUT"))
+        .and(path(format!("{subject_path}/CONSORTIUM")))
+        .and(query_param("label", "SITE_A_SUBJECT_A"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path(format!("{subject_path}/CONSORTIUM")))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(subject_path))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "ResultSet": {
+                "Result": [
+                    {"ID": "PROJECT_A", "label": "SUBJECT_A", "name": "Site A"},
+                    {"ID": "CONSORTIUM", "label": "SITE_A_SUBJECT_A", "name": "Consortium"},
+                ]
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let mut subject = Subject::default();
+    subject.project = Some("PROJECT_A".into());
+    subject.label = Some("SUBJECT_A".into());
+
+    let client = common::mock_client(&server);
+    client.share_into(&subject, "CONSORTIUM", Some("SITE_A_SUBJECT_A"), false).await.unwrap();
+
+    let projects = client.shared_projects(&subject).await.unwrap();
+    let labels = projects
+        .iter()
+        .map(|p| (p.id.as_deref().unwrap(), p.label.as_deref().unwrap()))
+        .collect::<Vec<_>>();
+    assert_eq!(labels, [("PROJECT_A", "SUBJECT_A"), ("CONSORTIUM", "SITE_A_SUBJECT_A")]);
+
+    client.unshare_from(&subject, "CONSORTIUM").await.unwrap();
+
+    let request = server
+        .received_requests()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|r| r.method.as_str() == "PUT")
+        .unwrap();
+    assert!(!request.url.query().unwrap_or_default().contains("primary"), "must only send `primary` when set");
+}
+
+#[tokio::test]
+async fn test_protocols_experiment_share01() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("PUT"))
+        .and(path("/data/experiments/XNAT_E00001/projects/CONSORTIUM"))
+        .and(query_param("primary", "true"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("DELETE"))
+        .and(path("/data/projects/PROJECT_A/subjects/SUBJECT_A/experiments/SESSION_A/projects/CONSORTIUM"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::mock_client(&server);
+    let mut experiment = Experiment::default();
+    experiment.id = Some("XNAT_E00001".into());
+    client.share_into(&experiment, "CONSORTIUM", None, true).await.unwrap();
+
+    experiment.project = Some("PROJECT_A".into());
+    experiment.subject_label = Some("SUBJECT_A".into());
+    experiment.label = Some("SESSION_A".into());
+    client.unshare_from(&experiment, "CONSORTIUM").await.unwrap();
+
+    let unnamed = client.shared_projects(&Experiment::default()).await;
+    assert!(matches!(unnamed, Err(Error::IdentifierRequired(_))), "must require an experiment identifier: {unnamed:?}");
+}
+
+/// A project member as XNAT lists them.
+fn member_row(username: &str, group: &str) -> serde_json::Value {
+    serde_json::json!({
+        "login": username,
+        "displayname": group,
+        "GROUP_ID": format!("PROJECT_A_{}", group.to_lowercase()),
+        "firstname": username.to_uppercase(),
+        "lastname": "Tester",
+        "email": format!("{username}@example.org"),
+        "xdat_user_id": 12,
+    })
+}
+
+#[tokio::test]
+async fn test_protocols_project_members01() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/data/projects/PROJECT_A/users"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "ResultSet": {
+                "Result": [
+                    member_row("alice", "Owners"),
+                    member_row("bob", "Members"),
+                    member_row("carol", "Collaborators"),
+                    member_row("dave", "Imaging Techs"),
+                ]
+            }
+        })))
+        .mount(&server)
+        .await;
+    for (verb, group, username) in [("PUT", "Collaborators", "bob"), ("PUT", "Members", "erin"), ("DELETE", "Collaborators", "carol")] {
+        Mock::given(method(verb))
+            .and(path(format!("/data/projects/PROJECT_A/users/{group}/{username}")))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+
+    let client = common::mock_client(&server);
+    let members = client.project_members("PROJECT_A").await.unwrap();
+    assert_eq!(members.len(), 4);
+    assert_eq!(members[0].display_name().as_deref(), Some("ALICE Tester"));
+    assert_eq!(members[0].project.as_deref(), Some("PROJECT_A"));
+    assert_eq!(members[3].group, Some(ProjectGroup::Custom("Imaging Techs".into())));
+
+    let desired = [
+        ProjectMember::new("alice", ProjectGroup::Owners),
+        ProjectMember::new("bob", ProjectGroup::Collaborators),
+        ProjectMember::new("erin", "Members"),
+        ProjectMember::new("dave", "Imaging Techs"),
+    ];
+    let changes = client.sync_members("PROJECT_A", &desired, true).await.unwrap();
+    assert_eq!(changes, [
+        MembershipChange::Moved {
+            username: "bob".into(),
+            from: ProjectGroup::Members,
+            to: ProjectGroup::Collaborators,
+        },
+        MembershipChange::Added { username: "erin".into(), group: ProjectGroup::Members },
+        MembershipChange::Removed { username: "carol".into(), group: ProjectGroup::Collaborators },
+    ]);
+}
+
+#[tokio::test]
+async fn test_protocols_project_members02() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/data/projects/PROJECT_A/users"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "ResultSet": { "Result": [member_row("alice", "Owners")] }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let client = common::mock_client(&server);
+    let desired = [ProjectMember::new("bob", ProjectGroup::Members), ProjectMember::default()];
+    let changes = client.sync_members("PROJECT_A", &desired, false).await;
+    assert!(matches!(changes, Err(Error::IdentifierRequired(_))), "must check every member before changing any: {changes:?}");
+
+    let desired = [ProjectMember::new("alice", ProjectGroup::Owners)];
+    let changes = client.sync_members("PROJECT_A", &desired, false).await.unwrap();
+    assert!(changes.is_empty(), "must not change members already in place");
+}
+
+fn user_row(username: &str, enabled: bool) -> serde_json::Value {
+    serde_json::json!({
+        "username": username,
+        "firstName": username.to_uppercase(),
+        "lastName": "Tester",
+        "email": format!("{username}@example.org"),
+        "id": 7,
+        "enabled": enabled,
+        "verified": true,
+        "lastModified": 1718000000000u64,
+        "lastSuccessfulLogin": null,
+    })
+}
+
+#[tokio::test]
+async fn test_protocols_site_users01() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/xapi/users/profiles"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            user_row("alice", true),
+            user_row("bob", false),
+        ])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/xapi/users/bob"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(user_row("bob", true)))
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/xapi/users"))
+        .and(body_string_contains("\"password\":\"s3cret\""))
+        .respond_with(ResponseTemplate::new(201))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("PUT"))
+        .and(path("/xapi/users/bob"))
+        .and(body_string_contains("\"email\":\"bob@example.com\""))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::mock_client(&server);
+    let users: Vec<User> = client.get_all().await.unwrap();
+    assert_eq!(users.len(), 2);
+    assert_eq!(users[1].enabled, Some(false));
+    assert_eq!(users[0].last_modified(), Some(1718000000000));
+    assert_eq!(users[0].last_successful_login(), None);
+
+    let mut user = User::new("carol");
+    user.password = Some("s3cret".into());
+    client.create_once(user).await.unwrap();
+
+    let mut user = User::new("bob");
+    user.email = Some("bob@example.com".into());
+    let stored = client.update_once(user).await.unwrap();
+    assert_eq!(stored.username.as_deref(), Some("bob"));
+    assert_eq!(stored.enabled, Some(true));
+    assert!(stored.password.is_none(), "must never read a password back");
+}
+
+#[tokio::test]
+async fn test_protocols_site_users02() {
+    common::init();
+
+    let server = common::mock_server().await;
+    for (verb, endpoint) in [
+        ("PUT", "enabled/false"),
+        ("PUT", "verified/true"),
+        ("PUT", "roles/Administrator"),
+        ("DELETE", "roles/NonExpiring"),
+        ("PUT", "groups/PROJECT_A_owner"),
+        ("DELETE", "groups/PROJECT_B_member"),
+    ] {
+        Mock::given(method(verb))
+            .and(path(format!("/xapi/users/alice/{endpoint}")))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path("/xapi/users/alice/roles"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(["Administrator"]))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/xapi/users/active/alice"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(["A1B2C3", "D4E5F6"]))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/xapi/users/active"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "alice": { "sessions": ["A1B2C3", "D4E5F6"], "count": 2 },
+        })))
+        .mount(&server)
+        .await;
+
+    let client = common::mock_client(&server);
+    client.set_enabled("alice", false).await.unwrap();
+    client.set_verified("alice", true).await.unwrap();
+    client.add_role("alice", "Administrator").await.unwrap();
+    client.remove_role("alice", "NonExpiring").await.unwrap();
+    client.add_group("alice", "PROJECT_A_owner").await.unwrap();
+    client.remove_group("alice", "PROJECT_B_member").await.unwrap();
+    assert_eq!(client.user_roles("alice").await.unwrap(), ["Administrator"]);
+
+    let sessions = client.active_sessions("alice").await.unwrap();
+    assert_eq!(sessions, ["A1B2C3", "D4E5F6"]);
+    let active = client.active_users().await.unwrap();
+    assert_eq!(active.get("alice"), Some(&sessions));
+}
+
+#[tokio::test]
+async fn test_protocols_site_users03() {
+    common::init();
+
+    // The host cannot page profiles, so they must
+    // be requested only once.
+    let server = common::mock_server().await;
+    let profiles = (0..5).map(|i| user_row(&format!("user{i}"), true)).collect::<Vec<_>>();
+    Mock::given(method("GET"))
+        .and(path("/xapi/users/profiles"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(profiles))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::mock_client(&server);
+    let model = User::default();
+    let mut stream = client.stream_from(&model, 2);
+
+    let mut found = vec![];
+    while let Some(user) = stream.next().await {
+        found.push(user.unwrap().username.unwrap());
+    }
+    assert_eq!(found, ["user0", "user1", "user2", "user3", "user4"]);
+    assert_eq!(stream.total_records(), Some(5));
+}
+
+#[tokio::test]
+async fn test_protocols_alias_tokens01() {
+    common::init();
+
+    let server = common::mock_server().await;
+    for (endpoint, username) in [("issue", "alice"), ("issue/user/bob", "bob")] {
+        Mock::given(method("GET"))
+            .and(path(format!("/data/services/tokens/{endpoint}")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "alias": format!("{username}-alias"),
+                "secret": "SECRET",
+                "estimatedExpirationTime": 1718000000000i64,
+                "xdatUserId": username,
+            })))
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path("/data/services/tokens/validate/bob-alias/SECRET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({ "valid": "bob" })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/data/services/tokens/validate/STALE/SECRET"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/data/services/tokens/invalidate/bob-alias/SECRET"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = common::mock_client(&server);
+    let token = client.issue_alias_token(None).await.unwrap();
+    assert_eq!(token.alias, "alice-alias");
+    assert_eq!(token.username.as_deref(), Some("alice"));
+    assert_eq!(token.expires_at().map(|t| t.timestamp()), Some(1718000000));
+    assert!(!format!("{token:?}").contains("SECRET"), "must not reveal the secret of a token");
+
+    let token = client.issue_alias_token(Some("bob")).await.unwrap();
+    assert_eq!(token.username.as_deref(), Some("bob"));
+    assert_eq!(client.validate_alias_token(&token).await.unwrap().as_deref(), Some("bob"));
+    assert_eq!(client.validate_alias_token(&AliasToken::new("STALE", "SECRET")).await.unwrap(), None);
+    client.invalidate_alias_token(&token).await.unwrap();
+}
+
+fn subscription_row(id: u64, name: &str, active: bool) -> serde_json::Value {
+    serde_json::json!({
+        "id": id,
+        "name": name,
+        "active": active,
+        "action-key": "org.nrg.xnat.eventservice.actions.EventServiceLoggingAction:EventServiceLoggingAction",
+        "attributes": {},
+        "event-filter": {
+            "event-type": "org.nrg.xnat.eventservice.events.SessionEvent",
+            "project-ids": ["PROJECT_A"],
+            "status": "CREATED",
+        },
+        "act-as-event-user": false,
+        "subscriber-user-id": "admin",
+        "valid": true,
+    })
+}
+
+#[tokio::test]
+async fn test_protocols_event_subscriptions01() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("POST"))
+        .and(path("/xapi/projects/PROJECT_A/events/subscription/validate"))
+        .and(body_string_contains("\"project-ids\":[\"PROJECT_A\"]"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/xapi/projects/PROJECT_A/events/subscription"))
+        .respond_with(ResponseTemplate::new(201).set_body_string("Log sessions"))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/xapi/projects/PROJECT_A/events/subscriptions"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            subscription_row(3, "Archive sessions", true),
+            subscription_row(4, "Log sessions", true),
+        ])))
+        .mount(&server)
+        .await;
+    for (verb, endpoint) in [("POST", "4/deactivate"), ("POST", "4/activate"), ("DELETE", "4")] {
+        Mock::given(method(verb))
+            .and(path(format!("/xapi/projects/PROJECT_A/events/subscription/{endpoint}")))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("POST"))
+        .and(path("/xapi/events/subscription/validate"))
+        .respond_with(ResponseTemplate::new(400).set_body_string("Missing action key"))
+        .mount(&server)
+        .await;
+
+    let client = common::mock_client(&server);
+    let filter = EventFilter::new("org.nrg.xnat.eventservice.events.SessionEvent", "CREATED")
+        .with_projects(&["PROJECT_A"]);
+    let subscription = EventSubscription::new(
+        "Log sessions",
+        "org.nrg.xnat.eventservice.actions.EventServiceLoggingAction:EventServiceLoggingAction",
+        filter,
+    );
+    client.validate_subscription(Some("PROJECT_A"), &subscription).await.unwrap();
+    let created = client.create_subscription(Some("PROJECT_A"), &subscription).await.unwrap();
+    assert_eq!(created.id, Some(4), "must look up the subscription XNAT created");
+    assert_eq!(created.subscriber().as_deref(), Some("admin"));
+
+    client.deactivate_subscription(Some("PROJECT_A"), 4).await.unwrap();
+    client.activate_subscription(Some("PROJECT_A"), 4).await.unwrap();
+    client.delete_subscription(Some("PROJECT_A"), 4).await.unwrap();
+
+    let invalid = client.validate_subscription(None, &EventSubscription::default()).await;
+    assert!(invalid.is_err(), "must report a subscription XNAT rejects");
+}
+
+#[tokio::test]
+async fn test_protocols_event_subscriptions02() {
+    common::init();
+
+    let server = common::mock_server().await;
+    Mock::given(method("GET"))
+        .and(path("/xapi/events/events"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+            "id": "org.nrg.xnat.eventservice.events.SessionEvent",
+            "display-name": "Image Session Event",
+            "description": "Image session created or updated.",
+            "statuses": ["CREATED", "UPDATED"],
+        }])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/xapi/events/actions"))
+        .and(query_param("event-type", "org.nrg.xnat.eventservice.events.SessionEvent"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([{
+            "id": "org.nrg.xnat.eventservice.actions.EventServiceLoggingAction",
+            "action-key": "org.nrg.xnat.eventservice.actions.EventServiceLoggingAction:EventServiceLoggingAction",
+            "display-name": "Logging Action",
+            "provider": "org.nrg.xnat.eventservice.actions.EventServiceLoggingAction",
+            "attributes": { "param": { "type": "string" } },
+        }])))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/xapi/events/delivered"))
+        .respond_with(ResponseTemplate::new(200).set_body_json((1..=5).map(|id| serde_json::json!({
+            "id": id,
+            "event-type": "org.nrg.xnat.eventservice.events.SessionEvent",
+            "project": "PROJECT_A",
+            "subscription": { "id": 4, "name": "Log sessions" },
+            "status": if id == 5 { "ACTION_FAILED" } else { "ACTION_COMPLETE" },
+            "timestamp": 1718000000000i64 + id,
+            "error-state": id == 5,
+        })).collect::<Vec<_>>()))
+        .expect(2)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/xapi/events/delivered/summary"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "event-types": { "org.nrg.xnat.eventservice.events.SessionEvent": 5 },
+            "projects": { "PROJECT_A": 5 },
+            "statuses": { "ACTION_COMPLETE": 4, "ACTION_FAILED": 1 },
+            "subscriptions": { "Log sessions": 5 },
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/xapi/events/delivered/count"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("5"))
+        .mount(&server)
+        .await;
+
+    let client = common::mock_client(&server);
+    let events = client.available_events(None).await.unwrap();
+    assert_eq!(events[0].statuses, ["CREATED", "UPDATED"]);
+    let actions = client
+        .available_actions(None, events[0].id.as_deref())
+        .await
+        .unwrap();
+    assert_eq!(actions[0].display_name.as_deref(), Some("Logging Action"));
+
+    let page = client.delivered_events(None, &Paging::new(2, 2)).await.unwrap();
+    assert_eq!(page.total_records, Some(5));
+    assert_eq!(page.results.iter().map(|d| d.id).collect::<Vec<_>>(), [Some(3), Some(4)]);
+
+    let page = client.delivered_events(None, &Paging::new(4, 2)).await.unwrap();
+    assert_eq!(page.results.len(), 1);
+    assert_eq!(page.results[0].error_state, Some(true));
+
+    let summary = client.delivery_summary(None).await.unwrap();
+    assert_eq!(summary.statuses.get("ACTION_FAILED"), Some(&1));
+    assert_eq!(client.delivery_count(None).await.unwrap(), 5);
+}
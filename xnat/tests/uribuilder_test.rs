@@ -93,6 +93,30 @@ fn test_version_v2_impls_events_subscription04() {
     assert_uri_eq!(uri, "xapi/events/subscription/SOME_ID");
 }
 
+#[test]
+fn test_version_v2_impls_events_subscription05() {
+    let events = V2.events().with_project("PROJECT_A");
+    let uri = events
+        .subscription()
+        .with_action(SubscriptionAction::Deactivate)
+        .with_id("SOME_ID")
+        .build();
+    assert_uri_eq!(uri, "xapi/projects/PROJECT_A/events/subscription/SOME_ID/deactivate");
+}
+
+#[test]
+fn test_version_v2_impls_events_delivered01() {
+    let events = V2.events();
+    let uri = events.delivered().build();
+    assert_uri_eq!(uri, "xapi/events/delivered");
+
+    let uri = events
+        .delivered()
+        .with_deliver_type(DeliveredType::Summary)
+        .build();
+    assert_uri_eq!(uri, "xapi/events/delivered/summary");
+}
+
 #[test]
 fn test_version_v2_impls_site_config01() {
     let uri = V2
@@ -196,3 +220,15 @@ fn test_version_v2_impls_session_data03() {
         .build();
     assert_uri_eq!(uri, "data/projects/some_project/experiments/some_session/scans/45");
 }
+
+#[test]
+fn test_version_v2_impls_prearchive01() {
+    let prearchive = V2.archive_data().prearchive();
+    let project = prearchive.projects().with_project("some_project");
+    let uri = project
+        .scans()
+        .with_timestamp("20240101_120000123")
+        .with_experiment("some_session")
+        .build();
+    assert_uri_eq!(uri, "data/prearchive/projects/some_project/20240101_120000123/some_session/scans");
+}
@@ -0,0 +1,912 @@
+//! Synchronous facade over the asynchronous
+//! `Xnat` client, for use in programs which do not
+//! otherwise manage an async runtime.
+//!
+//! Calls are driven to completion on a runtime
+//! shared by all blocking clients. Like
+//! `reqwest::blocking`, these methods must not be
+//! called from within an async context.
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::vec::IntoIter;
+
+use futures_core::Stream;
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::runtime::Runtime;
+
+use crate::client::timeouts::Timeouts;
+use crate::models::{
+    AliasToken,
+    AvailableEvent,
+    DeliveredEvent,
+    DeliverySummary,
+    EventAction,
+    EventSubscription,
+    PrearchiveSession, ProjectGroup, ProjectMember, Resource, Scan, SharedProject};
+use crate::protocols::{
+    self,
+    ArchiveOptions,
+    DownloadOptions,
+    ImportLocation,
+    ImportOptions,
+    MembershipChange,
+    Page,
+    Paging,
+    Query,
+    ResultStream,
+    UploadOptions,
+    UploadStatus,
+    ZipLayout,
+    ZipSource,
+};
+use crate::uri::admin::{AuthUriLegacy, EventsUri, UsersUri};
+use crate::uri::data::{ArchiveUri, ExperimentUri, ProjectUriLegacy, SubjectUriLegacy};
+use crate::{ClientAuth, ClientBuilderCore, ClientBuilderToken, UriBuilder, Version, XnatBuilder};
+
+/// Runtime which drives all requests made by
+/// blocking clients. It outlives every client so
+/// pooled connections remain usable.
+static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+/// Run a future to completion on the shared
+/// runtime.
+fn block_on<F: Future>(future: F) -> F::Output {
+    RUNTIME
+        .get_or_init(|| {
+            tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(1)
+                .thread_name("oxinat-blocking")
+                .enable_all()
+                .build()
+                .expect("must be able to start blocking runtime")
+        })
+        .block_on(future)
+}
+
+/// A synchronous XNAT client. Wraps, and defers
+/// all requests to, an asynchronous
+/// `oxinat_core::Xnat` client.
+#[derive(Clone, Debug)]
+pub struct Xnat<V: Version> {
+    inner: crate::Xnat<V>,
+}
+
+impl<V: Version + Clone> Xnat<V> {
+    /// Get the asynchronous client this client
+    /// defers to.
+    pub fn inner(&self) -> &crate::Xnat<V> {
+        &self.inner
+    }
+
+    /// Get the `JSESSIONID` cookie.
+    pub fn get_session_id(&self) -> String {
+        self.inner.get_session_id()
+    }
+
+    /// Sets the `JSESSIONID` cookie to this
+    /// XNAT client.
+    pub fn set_session_id(&mut self, value: &str) -> &Self {
+        self.inner.set_session_id(value);
+        self
+    }
+}
+
+impl<V: Version> From<crate::Xnat<V>> for Xnat<V> {
+    fn from(inner: crate::Xnat<V>) -> Self {
+        Self { inner }
+    }
+}
+
+/// Core behavior for a blocking `Xnat` client.
+/// Mirrors `oxinat_core::ClientCore`.
+pub trait ClientCore {
+    type Version: Version + Clone;
+
+    /// Initialize an `XnatBuilder` allowing
+    /// configuration of an XNAT client. Use
+    /// `ClientBuilderBlocking` to construct a
+    /// blocking client from it.
+    fn configure(hostname: &str) -> XnatBuilder<Self::Version>;
+    /// Create a new instance of an XNAT client.
+    fn new(base_url: &reqwest::Url, timeouts: &Option<Timeouts>, use_secure: bool, version: &Self::Version) -> Self;
+    /// Get the inner `Version` implementation.
+    fn version(&self) -> &Self::Version;
+}
+
+impl<V: Version + Clone> ClientCore for Xnat<V> {
+    type Version = V;
+
+    fn configure(hostname: &str) -> XnatBuilder<Self::Version> {
+        XnatBuilder::new(hostname)
+    }
+
+    fn new(base_url: &reqwest::Url, timeouts: &Option<Timeouts>, use_secure: bool, version: &Self::Version) -> Self {
+        let inner = <crate::Xnat<V> as crate::ClientCore>::new(base_url, timeouts, use_secure, version);
+        Self { inner }
+    }
+
+    fn version(&self) -> &Self::Version {
+        crate::ClientCore::version(&self.inner)
+    }
+}
+
+/// Methods for constructing blocking clients
+/// from an `XnatBuilder`.
+pub trait ClientBuilderBlocking {
+    type Client;
+
+    /// Attempt to build a blocking client from
+    /// this builder.
+    fn build_blocking(&self) -> crate::Result<Self::Client>;
+    /// Build a blocking client and acquire an
+    /// auth token for it.
+    ///
+    /// ```no_compile
+    /// use oxinat_core::*;
+    /// use oxinat_core::blocking::ClientBuilderBlocking;
+    ///
+    /// let client = Xnat::configure("xnat.host.org")
+    ///     .with_version(MyVersion)
+    ///     .with_password("my-password")
+    ///     .with_username("my-username")
+    ///     .acquire_blocking()?;
+    /// ```
+    fn acquire_blocking(&self) -> crate::Result<Self::Client>;
+}
+
+impl<V: Version + Clone> ClientBuilderBlocking for XnatBuilder<V>
+where
+    crate::Xnat<V>: ClientAuth,
+{
+    type Client = Xnat<V>;
+
+    fn build_blocking(&self) -> crate::Result<Self::Client> {
+        self.build().map(Xnat::from)
+    }
+
+    fn acquire_blocking(&self) -> crate::Result<Self::Client> {
+        block_on(self.acquire()).map(Xnat::from)
+    }
+}
+
+/// Methods necessary for managing auth tokens
+/// from an XNAT host. Mirrors
+/// `oxinat_core::ClientToken`.
+pub trait ClientToken: ClientCore {
+    /// Acquire an auth token from the XNAT host.
+    fn acquire(&mut self) -> crate::Result<()>;
+    /// Invalidates the auth token.
+    fn release(&mut self) -> crate::Result<()>;
+}
+
+impl<V: Version + Clone> ClientToken for Xnat<V>
+where
+    crate::Xnat<V>: crate::ClientToken,
+{
+    fn acquire(&mut self) -> crate::Result<()> {
+        block_on(crate::ClientToken::acquire(&mut self.inner))
+    }
+
+    fn release(&mut self) -> crate::Result<()> {
+        block_on(crate::ClientToken::release(&mut self.inner))
+    }
+}
+
+/// Type is able to make CREATE requests for a
+/// particular model. Mirrors
+/// `oxinat_core::protocols::Create`.
+pub trait Create<M>
+where
+    M: Clone + Send + Sync + Serialize,
+{
+    /// Attempt to send a CREATE request to the
+    /// XNAT server for **multiple** models.
+    fn create_many(&self, models: M) -> Vec<crate::Result<M>>
+    where
+        M: IntoIterator<Item = M, IntoIter = IntoIter<M>>;
+    /// Attempt to send a CREATE request to the
+    /// XNAT server for **one** model.
+    fn create_once(&self, model: M) -> crate::Result<M>;
+}
+
+impl<V, M> Create<M> for Xnat<V>
+where
+    V: Version + Clone,
+    M: Clone + Send + Sync + Serialize,
+    crate::Xnat<V>: protocols::Create<M>,
+{
+    fn create_many(&self, models: M) -> Vec<crate::Result<M>>
+    where
+        M: IntoIterator<Item = M, IntoIter = IntoIter<M>>,
+    {
+        protocols::Create::create_many(&self.inner, models)
+            .into_iter()
+            .map(block_on)
+            .collect()
+    }
+
+    fn create_once(&self, model: M) -> crate::Result<M> {
+        block_on(protocols::Create::create_once(&self.inner, model))
+    }
+}
+
+/// Type is able to make RETRIEVE requests for a
+/// particular model. Mirrors
+/// `oxinat_core::protocols::Retrieve`.
+pub trait Retrieve<M>
+where
+    M: Clone + Send + Sync + DeserializeOwned,
+{
+    /// Get all instances of a particular model
+    /// available to the user via the XNAT host.
+    fn get_all(&self) -> crate::Result<Vec<M>>
+    where
+        M: Default;
+    /// Get all instances of a particular model
+    /// using another model as the query
+    /// parameters for the request.
+    fn get_any_from(&self, model: &M) -> crate::Result<Vec<M>>;
+    /// Get one instance of a particular model
+    /// using another model as the query
+    /// parameters for the request.
+    fn get_one_from(&self, model: &M) -> crate::Result<M>;
+    /// Get one page of the instances of a
+    /// particular model using another model as
+    /// the query parameters for the request.
+    fn get_page_from(&self, model: &M, paging: &Paging) -> crate::Result<Page<M>>
+    where
+        M: Serialize;
+    /// Get the instances of a particular model
+    /// matching a query.
+    fn query(&self, query: &Query<M>) -> crate::Result<Page<M>>
+    where
+        M: Serialize;
+    /// Get the records matching a query as some
+    /// type `T`, deserialized from the columns the
+    /// host returned.
+    fn select<T>(&self, query: &Query<M>) -> crate::Result<Page<T>>
+    where
+        M: Serialize,
+        T: DeserializeOwned + Send;
+    /// Iterate over all instances of a particular
+    /// model using another model as the query
+    /// parameters, requesting `page_size`
+    /// instances at a time.
+    fn stream_from<'a>(&'a self, model: &'a M, page_size: u64) -> ResultIter<'a, M>
+    where
+        M: Serialize;
+    /// Iterate over all instances of a particular
+    /// model matching a query, requesting
+    /// `page_size` instances at a time.
+    fn stream_query<'a>(&'a self, query: Query<M>, page_size: u64) -> ResultIter<'a, M>
+    where
+        M: Serialize + 'a;
+}
+
+impl<V, M> Retrieve<M> for Xnat<V>
+where
+    V: Version + Clone,
+    M: Clone + Send + Sync + DeserializeOwned,
+    crate::Xnat<V>: protocols::Retrieve<M>,
+{
+    fn get_all(&self) -> crate::Result<Vec<M>>
+    where
+        M: Default,
+    {
+        block_on(protocols::Retrieve::get_all(&self.inner))
+    }
+
+    fn get_any_from(&self, model: &M) -> crate::Result<Vec<M>> {
+        block_on(protocols::Retrieve::get_any_from(&self.inner, model))
+    }
+
+    fn get_one_from(&self, model: &M) -> crate::Result<M> {
+        block_on(protocols::Retrieve::get_one_from(&self.inner, model))
+    }
+
+    fn get_page_from(&self, model: &M, paging: &Paging) -> crate::Result<Page<M>>
+    where
+        M: Serialize,
+    {
+        block_on(protocols::Retrieve::get_page_from(&self.inner, model, paging))
+    }
+
+    fn query(&self, query: &Query<M>) -> crate::Result<Page<M>>
+    where
+        M: Serialize,
+    {
+        block_on(protocols::Retrieve::query(&self.inner, query))
+    }
+
+    fn select<T>(&self, query: &Query<M>) -> crate::Result<Page<T>>
+    where
+        M: Serialize,
+        T: DeserializeOwned + Send,
+    {
+        block_on(protocols::Retrieve::select(&self.inner, query))
+    }
+
+    fn stream_from<'a>(&'a self, model: &'a M, page_size: u64) -> ResultIter<'a, M>
+    where
+        M: Serialize,
+    {
+        ResultIter { inner: protocols::Retrieve::stream_from(&self.inner, model, page_size) }
+    }
+
+    fn stream_query<'a>(&'a self, query: Query<M>, page_size: u64) -> ResultIter<'a, M>
+    where
+        M: Serialize + 'a,
+    {
+        ResultIter { inner: protocols::Retrieve::stream_query(&self.inner, query, page_size) }
+    }
+}
+
+/// Iterator over records requested from the host
+/// one page at a time. Mirrors
+/// `oxinat_core::protocols::ResultStream`.
+pub struct ResultIter<'a, M> {
+    inner: ResultStream<'a, M>,
+}
+
+impl<M> ResultIter<'_, M> {
+    /// Number of records yielded so far.
+    pub fn position(&self) -> u64 {
+        self.inner.position()
+    }
+
+    /// Number of records matching the request, if
+    /// the host reported it.
+    pub fn total_records(&self) -> Option<u64> {
+        self.inner.total_records()
+    }
+}
+
+impl<M> Iterator for ResultIter<'_, M> {
+    type Item = crate::Result<M>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        block_on(std::future::poll_fn(|cx| Pin::new(&mut self.inner).poll_next(cx)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Type is able to make UPDATE requests for a
+/// particular model. Mirrors
+/// `oxinat_core::protocols::Update`.
+pub trait Update<M>
+where
+    M: Clone + Send + Sync + Serialize,
+{
+    /// Attempt to send **multiple** UPDATE
+    /// requests to the XNAT host.
+    fn update_many(&self, models: M) -> Vec<crate::Result<M>>
+    where
+        M: IntoIterator<Item = M, IntoIter = IntoIter<M>>;
+    /// Attempt to send **one** UPDATE request to
+    /// the XNAT host.
+    fn update_once(&self, model: M) -> crate::Result<M>;
+}
+
+impl<V, M> Update<M> for Xnat<V>
+where
+    V: Version + Clone,
+    M: Clone + Send + Sync + Serialize,
+    crate::Xnat<V>: protocols::Update<M>,
+{
+    fn update_many(&self, models: M) -> Vec<crate::Result<M>>
+    where
+        M: IntoIterator<Item = M, IntoIter = IntoIter<M>>,
+    {
+        protocols::Update::update_many(&self.inner, models)
+            .into_iter()
+            .map(block_on)
+            .collect()
+    }
+
+    fn update_once(&self, model: M) -> crate::Result<M> {
+        block_on(protocols::Update::update_once(&self.inner, model))
+    }
+}
+
+/// Type is able to make DELETE requests for a
+/// particular model. Mirrors
+/// `oxinat_core::protocols::Delete`.
+pub trait Delete<M>
+where
+    M: Clone + Send + Sync + Serialize,
+{
+    /// Attempt to send **multiple** DELETE
+    /// requests to the XNAT host.
+    fn delete_many(&self, models: M) -> Vec<crate::Result<M>>
+    where
+        M: IntoIterator<Item = M, IntoIter = IntoIter<M>>;
+    /// Attempt to send **one** DELETE request to
+    /// the XNAT host.
+    fn delete_once(&self, model: M) -> crate::Result<M>;
+}
+
+impl<V, M> Delete<M> for Xnat<V>
+where
+    V: Version + Clone,
+    M: Clone + Send + Sync + Serialize,
+    crate::Xnat<V>: protocols::Delete<M>,
+{
+    fn delete_many(&self, models: M) -> Vec<crate::Result<M>>
+    where
+        M: IntoIterator<Item = M, IntoIter = IntoIter<M>>,
+    {
+        protocols::Delete::delete_many(&self.inner, models)
+            .into_iter()
+            .map(block_on)
+            .collect()
+    }
+
+    fn delete_once(&self, model: M) -> crate::Result<M> {
+        block_on(protocols::Delete::delete_once(&self.inner, model))
+    }
+}
+
+/// Type is able to upload files into a resource.
+/// Mirrors `oxinat_core::protocols::Upload`.
+pub trait Upload {
+    /// Upload a local file into a resource. `uri`
+    /// must name the file within the resource.
+    fn upload_file<UB>(&self, uri: &UB, path: &Path, options: &UploadOptions) -> crate::Result<()>
+    where
+        UB: UriBuilder + Sync;
+    /// Upload many local files into a resource in
+    /// a single multipart request. `uri` must name
+    /// the resource itself.
+    fn upload_files<UB>(&self, uri: &UB, paths: &[PathBuf], options: &UploadOptions) -> crate::Result<()>
+    where
+        UB: UriBuilder + Sync;
+}
+
+impl<V: Version + Clone> Upload for Xnat<V> {
+    fn upload_file<UB>(&self, uri: &UB, path: &Path, options: &UploadOptions) -> crate::Result<()>
+    where
+        UB: UriBuilder + Sync,
+    {
+        block_on(protocols::Upload::upload_file(&self.inner, uri, path, options))
+    }
+
+    fn upload_files<UB>(&self, uri: &UB, paths: &[PathBuf], options: &UploadOptions) -> crate::Result<()>
+    where
+        UB: UriBuilder + Sync,
+    {
+        block_on(protocols::Upload::upload_files(&self.inner, uri, paths, options))
+    }
+}
+
+/// Type is able to download files from a
+/// resource. Mirrors
+/// `oxinat_core::protocols::Download`.
+pub trait Download {
+    /// Download a resource file to `path`, or
+    /// every file of a resource into `path` as a
+    /// directory. Returns the paths written.
+    fn download_to(&self, model: &Resource, path: &Path, options: &DownloadOptions) -> crate::Result<Vec<PathBuf>>;
+    /// Download the files of `source` as a zip
+    /// archive written to `path`.
+    fn download_zip(&self, source: &ZipSource, path: &Path) -> crate::Result<()>;
+    /// Download the files of `source` as a zip
+    /// archive and extract them into `dir`, laid
+    /// out by `layout`. Returns the paths written.
+    fn download_extracted(&self, source: &ZipSource, dir: &Path, layout: &ZipLayout) -> crate::Result<Vec<PathBuf>>;
+    /// List the files of a resource.
+    fn list_files(&self, model: &Resource) -> crate::Result<Vec<Resource>>;
+}
+
+impl<V> Download for Xnat<V>
+where
+    V: Version + ExperimentUri + ProjectUriLegacy + SubjectUriLegacy + Clone + Send + Sync,
+{
+    fn download_to(&self, model: &Resource, path: &Path, options: &DownloadOptions) -> crate::Result<Vec<PathBuf>> {
+        block_on(protocols::Download::download_to(&self.inner, model, path, options))
+    }
+
+    fn download_zip(&self, source: &ZipSource, path: &Path) -> crate::Result<()> {
+        block_on(protocols::Download::download_zip(&self.inner, source, path))
+    }
+
+    fn download_extracted(&self, source: &ZipSource, dir: &Path, layout: &ZipLayout) -> crate::Result<Vec<PathBuf>> {
+        block_on(protocols::Download::download_extracted(&self.inner, source, dir, layout))
+    }
+
+    fn list_files(&self, model: &Resource) -> crate::Result<Vec<Resource>> {
+        block_on(protocols::Download::list_files(&self.inner, model))
+    }
+}
+
+/// Type is able to import sessions of DICOM
+/// files through the XNAT importer. Mirrors
+/// `oxinat_core::protocols::Import`.
+pub trait Import {
+    /// Import a zip or tar archive of files,
+    /// sent as a multipart upload.
+    fn import_archive(&self, path: &Path, options: &ImportOptions) -> crate::Result<ImportLocation>;
+    /// Import a single file, sent as the body of
+    /// the request.
+    fn import_file(&self, path: &Path, options: &ImportOptions) -> crate::Result<ImportLocation>;
+}
+
+impl<V> Import for Xnat<V>
+where
+    V: Version + ArchiveUri + Send + Sync,
+{
+    fn import_archive(&self, path: &Path, options: &ImportOptions) -> crate::Result<ImportLocation> {
+        block_on(protocols::Import::import_archive(&self.inner, path, options))
+    }
+
+    fn import_file(&self, path: &Path, options: &ImportOptions) -> crate::Result<ImportLocation> {
+        block_on(protocols::Import::import_file(&self.inner, path, options))
+    }
+}
+
+/// Type is able to triage sessions held in the
+/// prearchive. Mirrors
+/// `oxinat_core::protocols::Prearchive`.
+pub trait Prearchive {
+    /// List the sessions in the prearchive of a
+    /// project, or of every project if none is
+    /// given.
+    fn prearchive_sessions(&self, project: Option<&str>) -> crate::Result<Vec<PrearchiveSession>>;
+    /// Get a session from the prearchive, with
+    /// its `scan_count` filled in.
+    fn prearchive_session(&self, session: &PrearchiveSession) -> crate::Result<PrearchiveSession>;
+    /// List the scans of a session in the
+    /// prearchive.
+    fn prearchive_scans(&self, session: &PrearchiveSession) -> crate::Result<Vec<Scan>>;
+    /// Have XNAT rebuild a session from the files
+    /// it received.
+    fn rebuild_session(&self, session: &PrearchiveSession) -> crate::Result<()>;
+    /// Move a session into the prearchive of
+    /// another project.
+    fn move_session(&self, session: &PrearchiveSession, project: &str) -> crate::Result<()>;
+    /// Move a session into the archive. Returns
+    /// where it was archived.
+    fn archive_session(&self, session: &PrearchiveSession, options: &ArchiveOptions) -> crate::Result<ImportLocation>;
+    /// Delete a session from the prearchive.
+    fn delete_session(&self, session: &PrearchiveSession) -> crate::Result<()>;
+}
+
+impl<V> Prearchive for Xnat<V>
+where
+    V: Version + ArchiveUri + Send + Sync,
+{
+    fn prearchive_sessions(&self, project: Option<&str>) -> crate::Result<Vec<PrearchiveSession>> {
+        block_on(protocols::Prearchive::prearchive_sessions(&self.inner, project))
+    }
+
+    fn prearchive_session(&self, session: &PrearchiveSession) -> crate::Result<PrearchiveSession> {
+        block_on(protocols::Prearchive::prearchive_session(&self.inner, session))
+    }
+
+    fn prearchive_scans(&self, session: &PrearchiveSession) -> crate::Result<Vec<Scan>> {
+        block_on(protocols::Prearchive::prearchive_scans(&self.inner, session))
+    }
+
+    fn rebuild_session(&self, session: &PrearchiveSession) -> crate::Result<()> {
+        block_on(protocols::Prearchive::rebuild_session(&self.inner, session))
+    }
+
+    fn move_session(&self, session: &PrearchiveSession, project: &str) -> crate::Result<()> {
+        block_on(protocols::Prearchive::move_session(&self.inner, session, project))
+    }
+
+    fn archive_session(&self, session: &PrearchiveSession, options: &ArchiveOptions) -> crate::Result<ImportLocation> {
+        block_on(protocols::Prearchive::archive_session(&self.inner, session, options))
+    }
+
+    fn delete_session(&self, session: &PrearchiveSession) -> crate::Result<()> {
+        block_on(protocols::Prearchive::delete_session(&self.inner, session))
+    }
+}
+
+/// Type is able to check the status of uploads
+/// XNAT is processing. Mirrors
+/// `oxinat_core::protocols::TrackUpload`.
+pub trait TrackUpload {
+    /// Check the status of the upload started
+    /// with `id`.
+    fn upload_status(&self, id: &str) -> crate::Result<UploadStatus>;
+    /// Check the status of the upload started
+    /// with `id` until XNAT has finished with it,
+    /// or `timeout` has passed.
+    fn wait_until_complete(&self, id: &str, timeout: Duration) -> crate::Result<UploadStatus>;
+}
+
+impl<V> TrackUpload for Xnat<V>
+where
+    V: Version + ArchiveUri + Send + Sync,
+{
+    fn upload_status(&self, id: &str) -> crate::Result<UploadStatus> {
+        block_on(protocols::TrackUpload::upload_status(&self.inner, id))
+    }
+
+    fn wait_until_complete(&self, id: &str, timeout: Duration) -> crate::Result<UploadStatus> {
+        let handle = protocols::TrackUpload::track_upload(&self.inner, id);
+        block_on(handle.wait_until_complete(timeout))
+    }
+}
+
+/// Type is able to share models into projects
+/// other than the one which owns them. Mirrors
+/// `oxinat_core::protocols::Share`.
+pub trait Share<M>
+where
+    M: Sync,
+{
+    /// Share a model into `project`, optionally
+    /// under a different label.
+    fn share_into(&self, model: &M, project: &str, label: Option<&str>, primary: bool) -> crate::Result<()>;
+    /// Stop sharing a model into `project`.
+    fn unshare_from(&self, model: &M, project: &str) -> crate::Result<()>;
+    /// List every project a model belongs to.
+    fn shared_projects(&self, model: &M) -> crate::Result<Vec<SharedProject>>;
+}
+
+impl<V, M> Share<M> for Xnat<V>
+where
+    V: Version + Clone,
+    M: Sync,
+    crate::Xnat<V>: protocols::Share<M>,
+{
+    fn share_into(&self, model: &M, project: &str, label: Option<&str>, primary: bool) -> crate::Result<()> {
+        block_on(protocols::Share::share_into(&self.inner, model, project, label, primary))
+    }
+
+    fn unshare_from(&self, model: &M, project: &str) -> crate::Result<()> {
+        block_on(protocols::Share::unshare_from(&self.inner, model, project))
+    }
+
+    fn shared_projects(&self, model: &M) -> crate::Result<Vec<SharedProject>> {
+        block_on(protocols::Share::shared_projects(&self.inner, model))
+    }
+}
+
+/// Type is able to manage which users are members
+/// of a project. Mirrors
+/// `oxinat_core::protocols::Membership`.
+pub trait Membership {
+    /// List the members of a project.
+    fn project_members(&self, project: &str) -> crate::Result<Vec<ProjectMember>>;
+    /// Add a user to a group of a project.
+    fn add_member(&self, project: &str, username: &str, group: &ProjectGroup) -> crate::Result<()>;
+    /// Move a member of a project into another
+    /// group.
+    fn move_member(&self, project: &str, username: &str, group: &ProjectGroup) -> crate::Result<()>;
+    /// Remove a member from a group of a project.
+    fn remove_member(&self, project: &str, username: &str, group: &ProjectGroup) -> crate::Result<()>;
+    /// Bring the members of a project in line with
+    /// `members`. Returns the changes made.
+    fn sync_members(&self, project: &str, members: &[ProjectMember], prune: bool) -> crate::Result<Vec<MembershipChange>>;
+}
+
+impl<V> Membership for Xnat<V>
+where
+    V: Version + ProjectUriLegacy + Clone + Send + Sync,
+{
+    fn project_members(&self, project: &str) -> crate::Result<Vec<ProjectMember>> {
+        block_on(protocols::Membership::project_members(&self.inner, project))
+    }
+
+    fn add_member(&self, project: &str, username: &str, group: &ProjectGroup) -> crate::Result<()> {
+        block_on(protocols::Membership::add_member(&self.inner, project, username, group))
+    }
+
+    fn move_member(&self, project: &str, username: &str, group: &ProjectGroup) -> crate::Result<()> {
+        block_on(protocols::Membership::move_member(&self.inner, project, username, group))
+    }
+
+    fn remove_member(&self, project: &str, username: &str, group: &ProjectGroup) -> crate::Result<()> {
+        block_on(protocols::Membership::remove_member(&self.inner, project, username, group))
+    }
+
+    fn sync_members(&self, project: &str, members: &[ProjectMember], prune: bool) -> crate::Result<Vec<MembershipChange>> {
+        block_on(protocols::Membership::sync_members(&self.inner, project, members, prune))
+    }
+}
+
+/// Type is able to administer the accounts of an
+/// XNAT site. Mirrors
+/// `oxinat_core::protocols::UserAdmin`.
+pub trait UserAdmin {
+    /// Enable or disable an account.
+    fn set_enabled(&self, username: &str, enabled: bool) -> crate::Result<()>;
+    /// Mark the email of an account as verified,
+    /// or not.
+    fn set_verified(&self, username: &str, verified: bool) -> crate::Result<()>;
+    /// List the site roles of an account.
+    fn user_roles(&self, username: &str) -> crate::Result<Vec<String>>;
+    /// Assign a site role to an account.
+    fn add_role(&self, username: &str, role: &str) -> crate::Result<()>;
+    /// Take a site role away from an account.
+    fn remove_role(&self, username: &str, role: &str) -> crate::Result<()>;
+    /// List the groups an account belongs to.
+    fn user_groups(&self, username: &str) -> crate::Result<Vec<String>>;
+    /// Add an account to a group.
+    fn add_group(&self, username: &str, group: &str) -> crate::Result<()>;
+    /// Remove an account from a group.
+    fn remove_group(&self, username: &str, group: &str) -> crate::Result<()>;
+    /// List the ids of the sessions an account
+    /// has open.
+    fn active_sessions(&self, username: &str) -> crate::Result<Vec<String>>;
+    /// List the accounts with sessions open, and
+    /// the ids of those sessions.
+    fn active_users(&self) -> crate::Result<HashMap<String, Vec<String>>>;
+}
+
+impl<V> UserAdmin for Xnat<V>
+where
+    V: Version + UsersUri + Clone + Send + Sync,
+{
+    fn set_enabled(&self, username: &str, enabled: bool) -> crate::Result<()> {
+        block_on(protocols::UserAdmin::set_enabled(&self.inner, username, enabled))
+    }
+
+    fn set_verified(&self, username: &str, verified: bool) -> crate::Result<()> {
+        block_on(protocols::UserAdmin::set_verified(&self.inner, username, verified))
+    }
+
+    fn user_roles(&self, username: &str) -> crate::Result<Vec<String>> {
+        block_on(protocols::UserAdmin::user_roles(&self.inner, username))
+    }
+
+    fn add_role(&self, username: &str, role: &str) -> crate::Result<()> {
+        block_on(protocols::UserAdmin::add_role(&self.inner, username, role))
+    }
+
+    fn remove_role(&self, username: &str, role: &str) -> crate::Result<()> {
+        block_on(protocols::UserAdmin::remove_role(&self.inner, username, role))
+    }
+
+    fn user_groups(&self, username: &str) -> crate::Result<Vec<String>> {
+        block_on(protocols::UserAdmin::user_groups(&self.inner, username))
+    }
+
+    fn add_group(&self, username: &str, group: &str) -> crate::Result<()> {
+        block_on(protocols::UserAdmin::add_group(&self.inner, username, group))
+    }
+
+    fn remove_group(&self, username: &str, group: &str) -> crate::Result<()> {
+        block_on(protocols::UserAdmin::remove_group(&self.inner, username, group))
+    }
+
+    fn active_sessions(&self, username: &str) -> crate::Result<Vec<String>> {
+        block_on(protocols::UserAdmin::active_sessions(&self.inner, username))
+    }
+
+    fn active_users(&self) -> crate::Result<HashMap<String, Vec<String>>> {
+        block_on(protocols::UserAdmin::active_users(&self.inner))
+    }
+}
+
+/// Type is able to issue and revoke alias tokens.
+/// Mirrors `oxinat_core::protocols::AliasTokens`.
+pub trait AliasTokens {
+    /// Issue a token for the account of this
+    /// client or, if a username is given, for
+    /// another account.
+    fn issue_alias_token(&self, username: Option<&str>) -> crate::Result<AliasToken>;
+    /// Check whether XNAT still accepts a token.
+    /// Returns the username of the account the
+    /// token stands in for, if it does.
+    fn validate_alias_token(&self, token: &AliasToken) -> crate::Result<Option<String>>;
+    /// Revoke a token.
+    fn invalidate_alias_token(&self, token: &AliasToken) -> crate::Result<()>;
+}
+
+impl<V> AliasTokens for Xnat<V>
+where
+    V: Version + AuthUriLegacy + Clone + Send + Sync,
+{
+    fn issue_alias_token(&self, username: Option<&str>) -> crate::Result<AliasToken> {
+        block_on(protocols::AliasTokens::issue_alias_token(&self.inner, username))
+    }
+
+    fn validate_alias_token(&self, token: &AliasToken) -> crate::Result<Option<String>> {
+        block_on(protocols::AliasTokens::validate_alias_token(&self.inner, token))
+    }
+
+    fn invalidate_alias_token(&self, token: &AliasToken) -> crate::Result<()> {
+        block_on(protocols::AliasTokens::invalidate_alias_token(&self.inner, token))
+    }
+}
+
+/// Type is able to manage subscriptions to the
+/// XNAT event service. Mirrors
+/// `oxinat_core::protocols::EventSubscriptions`.
+pub trait EventSubscriptions {
+    /// List the subscriptions of a project, or
+    /// the site-wide subscriptions.
+    fn event_subscriptions(&self, project: Option<&str>) -> crate::Result<Vec<EventSubscription>>;
+    /// Get a subscription by its id.
+    fn event_subscription(&self, project: Option<&str>, id: u64) -> crate::Result<EventSubscription>;
+    /// Create a subscription. Returns the
+    /// subscription as XNAT stored it.
+    fn create_subscription(&self, project: Option<&str>, subscription: &EventSubscription) -> crate::Result<EventSubscription>;
+    /// Check a subscription without creating it.
+    fn validate_subscription(&self, project: Option<&str>, subscription: &EventSubscription) -> crate::Result<()>;
+    /// Activate a subscription.
+    fn activate_subscription(&self, project: Option<&str>, id: u64) -> crate::Result<()>;
+    /// Deactivate a subscription.
+    fn deactivate_subscription(&self, project: Option<&str>, id: u64) -> crate::Result<()>;
+    /// Delete a subscription.
+    fn delete_subscription(&self, project: Option<&str>, id: u64) -> crate::Result<()>;
+    /// List the types of event subscriptions can
+    /// filter on.
+    fn available_events(&self, project: Option<&str>) -> crate::Result<Vec<AvailableEvent>>;
+    /// List the actions subscriptions can carry
+    /// out.
+    fn available_actions(&self, project: Option<&str>, event_type: Option<&str>) -> crate::Result<Vec<EventAction>>;
+    /// Get one window of the events delivered to
+    /// subscriptions.
+    fn delivered_events(&self, project: Option<&str>, paging: &Paging) -> crate::Result<Page<DeliveredEvent>>;
+    /// Count the events delivered to
+    /// subscriptions several ways.
+    fn delivery_summary(&self, project: Option<&str>) -> crate::Result<DeliverySummary>;
+    /// Count the events delivered to
+    /// subscriptions.
+    fn delivery_count(&self, project: Option<&str>) -> crate::Result<u64>;
+}
+
+impl<V> EventSubscriptions for Xnat<V>
+where
+    V: Version + EventsUri + Clone + Send + Sync,
+{
+    fn event_subscriptions(&self, project: Option<&str>) -> crate::Result<Vec<EventSubscription>> {
+        block_on(protocols::EventSubscriptions::event_subscriptions(&self.inner, project))
+    }
+
+    fn event_subscription(&self, project: Option<&str>, id: u64) -> crate::Result<EventSubscription> {
+        block_on(protocols::EventSubscriptions::event_subscription(&self.inner, project, id))
+    }
+
+    fn create_subscription(&self, project: Option<&str>, subscription: &EventSubscription) -> crate::Result<EventSubscription> {
+        block_on(protocols::EventSubscriptions::create_subscription(&self.inner, project, subscription))
+    }
+
+    fn validate_subscription(&self, project: Option<&str>, subscription: &EventSubscription) -> crate::Result<()> {
+        block_on(protocols::EventSubscriptions::validate_subscription(&self.inner, project, subscription))
+    }
+
+    fn activate_subscription(&self, project: Option<&str>, id: u64) -> crate::Result<()> {
+        block_on(protocols::EventSubscriptions::activate_subscription(&self.inner, project, id))
+    }
+
+    fn deactivate_subscription(&self, project: Option<&str>, id: u64) -> crate::Result<()> {
+        block_on(protocols::EventSubscriptions::deactivate_subscription(&self.inner, project, id))
+    }
+
+    fn delete_subscription(&self, project: Option<&str>, id: u64) -> crate::Result<()> {
+        block_on(protocols::EventSubscriptions::delete_subscription(&self.inner, project, id))
+    }
+
+    fn available_events(&self, project: Option<&str>) -> crate::Result<Vec<AvailableEvent>> {
+        block_on(protocols::EventSubscriptions::available_events(&self.inner, project))
+    }
+
+    fn available_actions(&self, project: Option<&str>, event_type: Option<&str>) -> crate::Result<Vec<EventAction>> {
+        block_on(protocols::EventSubscriptions::available_actions(&self.inner, project, event_type))
+    }
+
+    fn delivered_events(&self, project: Option<&str>, paging: &Paging) -> crate::Result<Page<DeliveredEvent>> {
+        block_on(protocols::EventSubscriptions::delivered_events(&self.inner, project, paging))
+    }
+
+    fn delivery_summary(&self, project: Option<&str>) -> crate::Result<DeliverySummary> {
+        block_on(protocols::EventSubscriptions::delivery_summary(&self.inner, project))
+    }
+
+    fn delivery_count(&self, project: Option<&str>) -> crate::Result<u64> {
+        block_on(protocols::EventSubscriptions::delivery_count(&self.inner, project))
+    }
+}
@@ -1,19 +1,32 @@
+use std::time::Duration;
+
 use async_trait::async_trait;
 
-use crate::Version;
+use crate::{Error, Version};
+use super::network::{CertificateFormat, Network};
+use super::preflight::{Preflight, PreflightMode};
+use super::retry::RetryPolicy;
+use super::session::{Credentials, Reauth, ReauthEvent, ReauthHook};
 use super::timeouts::Timeouts;
-use super::clients::{ClientAuth, ClientCore, Xnat};
+use super::clients::{join_url, ClientAuth, ClientCore, Xnat};
 
 /// A building pattern type meant for constructing
 /// an XNAT client.
 #[allow(dead_code)]
 pub struct XnatBuilder<V: Version> {
-    hostname:   String,
-    password:   Option<String>,
-    timeouts:   Option<Timeouts>,
-    username:   Option<String>,
-    use_secure: bool,
-    version:    Option<V>,
+    context_path: Option<String>,
+    hostname:     String,
+    network:      Network,
+    password:     Option<String>,
+    port:         Option<u16>,
+    preflight:    Preflight,
+    reauth:       bool,
+    reauth_hook:  Option<ReauthHook>,
+    retry:        RetryPolicy,
+    timeouts:     Option<Timeouts>,
+    username:     Option<String>,
+    use_secure:   Option<bool>,
+    version:      Option<V>,
 }
 
 /// Internal usage only. Dictates how a URL should
@@ -24,20 +37,37 @@ enum UrlKind<'a> {
 }
 
 impl<V: Version + Clone> XnatBuilder<V> {
-    fn base_url(&self, kind: UrlKind) -> anyhow::Result<reqwest::Url> {
-        let mut host = reqwest::Url::parse("http://stud")?;
-        host.set_host(Some(&self.hostname.clone()))?;
-        host.set_scheme(if self.use_secure {
+    fn base_url(&self, kind: UrlKind) -> crate::Result<reqwest::Url> {
+        // The host name may be given as a full base
+        // URL, including a port and the context path
+        // XNAT is deployed under.
+        let host = if self.hostname.contains("://") {
+            self.hostname.to_owned()
+        } else {
+            format!("http://{}", self.hostname)
+        };
+        let invalid = || Error::Config(format!("invalid host name `{}`", self.hostname));
+        let mut host = reqwest::Url::parse(&host)
+            .map_err(|e| Error::Config(format!("invalid host name `{}`: {e}", self.hostname)))?;
+        host.set_query(None);
+        host.set_fragment(None);
+        host.set_scheme(if self.is_secure() {
             "https"
         } else {
             "http"
-        }).unwrap();
+        }).map_err(|_| invalid())?;
+        if self.port.is_some() {
+            host.set_port(self.port).map_err(|_| invalid())?;
+        }
+        if let Some(path) = &self.context_path {
+            host.set_path(path);
+        }
 
         Ok(match kind {
             UrlKind::Basic => host,
             UrlKind::Credentialed(u, p) => {
-                host.set_password(p).unwrap();
-                host.set_username(u).unwrap();
+                host.set_password(p).map_err(|_| invalid())?;
+                host.set_username(u).map_err(|_| invalid())?;
                 host
             }
         })
@@ -50,7 +80,24 @@ impl<V: Version + Clone> XnatBuilder<V> {
         )
     }
 
-    fn version(&self) -> anyhow::Result<V> {
+    /// Whether constructed clients use secure
+    /// protocols. Unless set explicitly, this is
+    /// inferred from the scheme of the host name.
+    fn is_secure(&self) -> bool {
+        self.use_secure
+            .unwrap_or(self.hostname.starts_with("https://"))
+    }
+
+    /// Credentials to be kept by constructed
+    /// clients for renewing expired sessions.
+    fn retained_credentials(&self) -> Option<Credentials> {
+        self.username
+            .as_deref()
+            .filter(|_| self.reauth)
+            .map(|u| Credentials::new(u, self.password.as_deref()))
+    }
+
+    fn version(&self) -> crate::Result<V> {
         Ok(self.version.as_ref().cloned().unwrap())
     }
 }
@@ -75,7 +122,7 @@ pub trait ClientBuilderCore {
     ///     .with_password("my-password")
     ///     .with_username("my-username");
     /// ```
-    fn build(&self) -> anyhow::Result<Self::Client>;
+    fn build(&self) -> crate::Result<Self::Client>;
     /// Initialize a new builder instance.
     fn new(hostname: &str) -> Self;
 }
@@ -83,23 +130,42 @@ pub trait ClientBuilderCore {
 impl<V: Version + Clone> ClientBuilderCore for XnatBuilder<V> {
     type Client = Xnat<V>;
 
-    fn build(&self) -> anyhow::Result<Self::Client> {
-        Ok(Xnat::new(
+    fn build(&self) -> crate::Result<Self::Client> {
+        let mut client = Xnat::new(
             &self.base_url(UrlKind::Basic)?,
             &self.timeouts,
-            self.use_secure,
+            self.is_secure(),
             &self.version()?,
-        ))
+        );
+        client.set_preflight(&self.preflight);
+        client.set_reauth(&Reauth::default()
+            .with_credentials(self.retained_credentials())
+            .with_hook(self.reauth_hook.clone()));
+        client.set_retry(&self.retry);
+        client.set_network(&self.network);
+
+        // Construct the REST client early so that
+        // invalid certificates or proxies are
+        // reported here.
+        client.client()?;
+        Ok(client)
     }
 
     fn new(hostname: &str) -> Self {
         XnatBuilder{
-            hostname:   hostname.to_owned(),
-            password:   None,
-            timeouts:   None,
-            username:   None,
-            use_secure: false,
-            version:    None
+            context_path: None,
+            hostname:     hostname.to_owned(),
+            network:      Network::default(),
+            password:     None,
+            port:         None,
+            preflight:    Preflight::default(),
+            reauth:       false,
+            reauth_hook:  None,
+            retry:        RetryPolicy::default(),
+            timeouts:     None,
+            username:     None,
+            use_secure:   None,
+            version:      None
         }
     }
 }
@@ -112,12 +178,74 @@ pub trait ClientBuilderAttrs: ClientBuilderCore {
     /// Set whether constructed clients should
     /// use secure protocols and verify SSL certs.
     fn use_secure(self, value: bool) -> Self;
+    /// Set whether constructed clients accept
+    /// invalid certificates and host names from
+    /// the host, while still requiring HTTPS.
+    fn use_insecure(self, value: bool) -> Self;
+    /// Set an alias token, issued by the host, to
+    /// be used in place of the auth username and
+    /// password for constructed clients.
+    fn with_alias_token(self, alias: &str, secret: &str) -> Self;
+    /// Set the path XNAT is deployed under on
+    /// the host, such as `/xnat`. Overrides any
+    /// path given as part of the host name.
+    fn with_context_path(self, path: &str) -> Self;
     /// Set the host name that will be assigned
-    /// to constructed clients.
+    /// to constructed clients. May also be a
+    /// full base URL, such as
+    /// `https://xnat.host.org:8443/xnat`.
     fn with_hostname(self, hostname: &str) -> Self;
+    /// Set a client certificate chain and PKCS#8
+    /// private key, both PEM encoded, presented
+    /// by constructed clients for mutual TLS.
+    fn with_identity_pem(self, cert: &[u8], key: &[u8]) -> Self;
+    /// Set a DER encoded PKCS#12 archive
+    /// presented by constructed clients for
+    /// mutual TLS.
+    fn with_identity_pkcs12(self, der: &[u8], password: &str) -> Self;
+    /// Set the hosts, in `NO_PROXY` form, for
+    /// which constructed clients bypass the
+    /// proxy, including proxies taken from the
+    /// environment. `*` disables all proxies.
+    fn with_no_proxy(self, hosts: &str) -> Self;
     /// Set the auth password to be used for
     /// token acquisition for constructed clients.
     fn with_password(self, password: &str) -> Self;
+    /// Set the port constructed clients connect
+    /// to on the host. Overrides any port given
+    /// as part of the host name.
+    fn with_port(self, port: u16) -> Self;
+    /// Set how constructed clients check that an
+    /// endpoint supports a REST method prior to
+    /// making a request. Defaults to
+    /// `PreflightMode::Cached`.
+    fn with_preflight(self, mode: PreflightMode) -> Self;
+    /// Set how long constructed clients keep a
+    /// cached pre-flight check before checking
+    /// the endpoint again.
+    fn with_preflight_ttl(self, ttl: Duration) -> Self;
+    /// Set whether constructed clients keep the
+    /// auth username and password, allowing them
+    /// to acquire a new session when the current
+    /// one expires.
+    fn with_reauth(self, value: bool) -> Self;
+    /// Set a callback invoked each time a
+    /// constructed client renews an expired
+    /// session.
+    fn with_reauth_hook<F>(self, hook: F) -> Self
+    where
+        F: Fn(&ReauthEvent) + Send + Sync + 'static;
+    /// Set a proxy through which constructed
+    /// clients send all requests.
+    fn with_proxy(self, url: &str) -> Self;
+    /// Set how constructed clients retry
+    /// idempotent requests which fail for
+    /// transient reasons.
+    fn with_retry(self, policy: &RetryPolicy) -> Self;
+    /// Add a certificate authority constructed
+    /// clients trust in addition to the system
+    /// roots.
+    fn with_root_certificate(self, data: &[u8], format: CertificateFormat) -> Self;
     /// Set the timeout values (connect & read) to
     /// be assigned to constructed clients.
     fn with_timeouts(self, timeouts: &Timeouts) -> Self;
@@ -133,7 +261,24 @@ impl<V: Version + Clone> ClientBuilderAttrs for XnatBuilder<V> {
     type Version = V;
 
     fn use_secure(mut self, value: bool) -> Self {
-        self.use_secure = value;
+        self.use_secure = Some(value);
+        self
+    }
+
+    fn use_insecure(mut self, value: bool) -> Self {
+        self.network = self.network.with_insecure(value);
+        self
+    }
+
+    fn with_alias_token(self, alias: &str, secret: &str) -> Self {
+        // XNAT accepts an alias and its secret
+        // wherever it accepts a username and
+        // password.
+        self.with_username(alias).with_password(secret)
+    }
+
+    fn with_context_path(mut self, path: &str) -> Self {
+        self.context_path.clone_from(&Some(path.to_owned()));
         self
     }
 
@@ -142,11 +287,69 @@ impl<V: Version + Clone> ClientBuilderAttrs for XnatBuilder<V> {
         self
     }
 
+    fn with_identity_pem(mut self, cert: &[u8], key: &[u8]) -> Self {
+        self.network = self.network.with_identity_pem(cert, key);
+        self
+    }
+
+    fn with_identity_pkcs12(mut self, der: &[u8], password: &str) -> Self {
+        self.network = self.network.with_identity_pkcs12(der, password);
+        self
+    }
+
+    fn with_no_proxy(mut self, hosts: &str) -> Self {
+        self.network = self.network.with_no_proxy(hosts);
+        self
+    }
+
     fn with_password(mut self, password: &str) -> Self {
         self.password.clone_from(&Some(password.to_owned()));
         self
     }
 
+    fn with_port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    fn with_preflight(mut self, mode: PreflightMode) -> Self {
+        self.preflight = self.preflight.with_mode(mode);
+        self
+    }
+
+    fn with_preflight_ttl(mut self, ttl: Duration) -> Self {
+        self.preflight = self.preflight.with_ttl(ttl);
+        self
+    }
+
+    fn with_reauth(mut self, value: bool) -> Self {
+        self.reauth = value;
+        self
+    }
+
+    fn with_reauth_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&ReauthEvent) + Send + Sync + 'static,
+    {
+        self.reauth_hook = Some(std::sync::Arc::new(hook));
+        self
+    }
+
+    fn with_proxy(mut self, url: &str) -> Self {
+        self.network = self.network.with_proxy(url);
+        self
+    }
+
+    fn with_retry(mut self, policy: &RetryPolicy) -> Self {
+        self.retry.clone_from(policy);
+        self
+    }
+
+    fn with_root_certificate(mut self, data: &[u8], format: CertificateFormat) -> Self {
+        self.network = self.network.with_root_certificate(data, format);
+        self
+    }
+
     fn with_timeouts(mut self, timeouts: &Timeouts) -> Self {
         self.timeouts.clone_from(&Some(timeouts.to_owned()));
         self
@@ -163,7 +366,7 @@ impl<V: Version + Clone> ClientBuilderAttrs for XnatBuilder<V> {
     }
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 pub trait ClientBuilderToken: ClientBuilderCore
 where
     Self::Client: ClientAuth,
@@ -186,18 +389,18 @@ where
     /// 
     /// let client = builder.acquire().await?;
     /// ```
-    async fn acquire(&self) -> anyhow::Result<Self::Client>;
+    async fn acquire(&self) -> crate::Result<Self::Client>;
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 impl<V: Version + Clone> ClientBuilderToken for XnatBuilder<V>
 where
     Self::Client: ClientAuth,
 {
-    async fn acquire(&self) -> anyhow::Result<Self::Client> {
+    async fn acquire(&self) -> crate::Result<Self::Client> {
         let mut client = self.build()?;
-        let mut base_url = self.base_url(self.credentials())?;
-        base_url.set_path(&client.auth_uri()?);
+        let base_url = self.base_url(self.credentials())?;
+        let base_url = join_url(&base_url, &client.auth_uri()?);
 
         let res = client
             .client()?
@@ -1,71 +1,204 @@
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, RwLock};
 
 use async_trait::async_trait;
-use reqwest::{header::HeaderValue, redirect::Policy, Method};
+use reqwest::{header::HeaderValue, redirect::Policy, Method, StatusCode};
 
 use crate::{
-    AuthUriLegacy, BuildResult, UriBuilder, Version
+    AuthUriLegacy, BuildResult, Error, ErrorResponse, UriBuilder, Version
 };
+use crate::error::RequestMethod;
+use crate::uri::admin::auth::AuthUriLegacyBuilder;
 use super::builder::{ClientBuilderCore, XnatBuilder};
-use super::error::ClientError;
+use super::network::Network;
+use super::preflight::{parse_allow, Capabilities, Preflight, PreflightMode};
+use super::retry::RetryPolicy;
+use super::session::{session_expired, Reauth, ReauthEvent, ReauthReason};
 use super::timeouts::Timeouts;
 
-type RequestBuilderResult = anyhow::Result<reqwest::RequestBuilder>;
+type RequestBuilderResult = crate::Result<reqwest::RequestBuilder>;
 
 #[derive(Clone, Debug)]
 #[allow(dead_code)]
 pub struct Xnat<V: Version> {
-    base_url:   reqwest::Url,
+    base_url:     reqwest::Url,
+    capabilities: Arc<Capabilities>,
+    connection:   Arc<Mutex<Connection>>,
+    network:      Network,
+    preflight:    Preflight,
+    reauth:       Reauth,
+    retry:        RetryPolicy,
+    session_id:   Arc<RwLock<Option<String>>>,
+    timeouts:     Timeouts,
+    use_secure:   bool,
+    version:      V,
+}
+
+/// Pooled REST client shared between clones of
+/// an `Xnat` client. The inner client is built
+/// on first use and then reused until the
+/// session it was built for changes.
+#[derive(Debug, Default)]
+struct Connection {
+    client:     Option<reqwest::Client>,
     session_id: Option<String>,
-    timeouts:   Timeouts,
-    use_secure: bool,
-    version:    V,
 }
 
 impl<V: Version> Xnat<V> {
     /// Get the `JSESSIONID` cookie.
     pub fn get_session_id(&self) -> String {
-        self.session_id.as_ref().unwrap().to_owned()
+        self.session_id().unwrap()
+    }
+
+    /// Sets the credentials, and the callback
+    /// invoked afterwards, used to acquire a new
+    /// session once the current one expires.
+    pub fn set_reauth(&mut self, reauth: &Reauth) -> &Self {
+        self.reauth.clone_from(reauth);
+        self
+    }
+
+    /// Sets the TLS and proxy settings used to
+    /// connect to the host.
+    pub fn set_network(&mut self, network: &Network) -> &Self {
+        self.network.clone_from(network);
+        self.connection = Arc::default();
+        self
+    }
+
+    /// Sets how this client checks whether an
+    /// endpoint supports a REST method before
+    /// making a request.
+    pub fn set_preflight(&mut self, preflight: &Preflight) -> &Self {
+        self.preflight.clone_from(preflight);
+        self
+    }
+
+    /// Sets how this client retries requests
+    /// which fail for transient reasons.
+    pub fn set_retry(&mut self, retry: &RetryPolicy) -> &Self {
+        self.retry.clone_from(retry);
+        self
     }
 
     /// Sets the `JSESSIONID` cookie to this
     /// XNAT client.
     pub fn set_session_id(&mut self, value: &str) -> &Self {
-        self.session_id.clone_from(&Some(value.to_string()));
+        self.replace_session_id(Some(value.to_string()));
         self
     }
 
+    /// Acquire a new session from the host using
+    /// the retained credentials.
+    async fn reauthenticate(&self, url: &reqwest::Url, reason: ReauthReason) -> crate::Result<()>
+    where
+        V: Clone,
+    {
+        let credentials = self
+            .reauth
+            .credentials()
+            .ok_or_else(|| Error::from_status(ErrorResponse::from_status(StatusCode::UNAUTHORIZED)))?;
+        log::info!("session expired ({reason}) requesting `{url}`, acquiring new session");
+
+        let auth_url = self.url_for(&self.jsessionid_uri()?);
+        let res = self
+            .client()?
+            .post(auth_url)
+            .basic_auth(credentials.username(), credentials.password())
+            .send()
+            .await?;
+        let token = tokacq_validator(res).await?;
+        self.replace_session_id(Some(token));
+
+        self.reauth.notify(&ReauthEvent { url: url.to_owned(), reason });
+        Ok(())
+    }
+
+    /// Determine if the response came from an
+    /// expired session and, if so, acquire a new
+    /// one. Returns whether the request should be
+    /// sent again.
+    async fn session_needs_renewal(
+        &self,
+        url: &reqwest::Url,
+        res: &reqwest::Response,
+        session_id: &Option<String>
+    ) -> crate::Result<bool>
+    where
+        V: Clone,
+    {
+        let Some(reason) = session_expired(url, res) else {
+            return Ok(false)
+        };
+        if self.reauth.credentials().is_none() || url.path() == self.url_for(&self.jsessionid_uri()?).path() {
+            return Ok(false)
+        }
+
+        // Another clone may have already renewed
+        // the session while this request was in
+        // flight.
+        if self.session_id() == *session_id {
+            self.reauthenticate(url, reason).await?;
+        }
+        Ok(true)
+    }
+
+    /// Swap out the `JSESSIONID` shared by this
+    /// client and its clones.
+    fn replace_session_id(&self, value: Option<String>) -> Option<String> {
+        match self.session_id.write() {
+            Ok(mut session_id) => std::mem::replace(&mut session_id, value),
+            Err(_) => None,
+        }
+    }
+
+    /// Get the `JSESSIONID` cookie, if one has
+    /// been acquired.
+    fn session_id(&self) -> Option<String> {
+        self.session_id.read().ok().and_then(|s| s.to_owned())
+    }
+
+    /// The endpoint used to acquire a new
+    /// session.
+    fn jsessionid_uri(&self) -> BuildResult {
+        AuthUriLegacyBuilder::from_parent(self.version.data_uri().into()).build_jsessionid()
+    }
+
     /// Returns a clone of the base URL to the
     /// XNAT host.
     fn base_url(&self) -> reqwest::Url {
         self.base_url.clone()
     }
 
+    /// Construct the full URL to some endpoint,
+    /// keeping any context path the host is
+    /// deployed under.
+    fn url_for(&self, path: &str) -> reqwest::Url {
+        join_url(&self.base_url, path)
+    }
+
     /// Initializes a `Jar` for cookie storage.
     fn cookie_jar(&self) -> Arc<reqwest::cookie::Jar> {
         let url = self.base_url();
         let jar = reqwest::cookie::Jar::default();
 
-        if self.session_id.is_some() {
-            jar.add_cookie_str(
-                &format!("JSESSIONID={}", self.get_session_id()),
-                &url
-            );
+        if let Some(session_id) = self.session_id() {
+            jar.add_cookie_str(&format!("JSESSIONID={session_id}"), &url);
         }
         jar.into()
     }
 
-    /// Builds a blocking client needed for
+    /// Builds a client builder needed for
     /// configuring a new client.
-    fn new_client_builder(&self) -> reqwest::ClientBuilder {
-        reqwest::ClientBuilder::new()
+    fn new_client_builder(&self) -> crate::Result<reqwest::ClientBuilder> {
+        let builder = reqwest::ClientBuilder::new()
             .connect_timeout(self.timeouts.connect())
             .cookie_provider(self.cookie_jar())
-            .danger_accept_invalid_certs(!self.use_secure)
+            .danger_accept_invalid_certs(!self.use_secure || self.network.insecure())
             .https_only(self.use_secure)
             .read_timeout(self.timeouts.read())
             .redirect(Policy::default())
-            .user_agent(super::APP_USER_AGENT)
+            .user_agent(super::APP_USER_AGENT);
+        self.network.configure(builder)
     }
 }
 
@@ -75,8 +208,10 @@ impl<V: Version> Xnat<V> {
 pub trait ClientCore {
     type Version: Version + Clone;
 
-    /// Constructs a REST client.
-    fn client(&self) -> anyhow::Result<reqwest::Client>;
+    /// Get the pooled REST client, constructing
+    /// it if one is not yet available for the
+    /// current session.
+    fn client(&self) -> crate::Result<reqwest::Client>;
     /// Initialize an `XnatBuilder` allowing
     /// configuration of an XNAT client.
     /// 
@@ -102,19 +237,33 @@ pub trait ClientCore {
 impl<V: Version + Clone> ClientCore for Xnat<V> {
     type Version = V;
 
-    fn client(&self) -> anyhow::Result<reqwest::Client> {
-        Ok(self
-            .new_client_builder()
-            .build()
-            .map(|c| {
-                log::debug!("configured internal REST client");
-                c
-            })
-            .map_err(|c| {
-                log::error!("could not configure REST client");
-                c
-            })?
-        )
+    fn client(&self) -> crate::Result<reqwest::Client> {
+        let mut connection = self
+            .connection
+            .lock()
+            .map_err(|_| Error::Config("REST client connection is poisoned".into()))?;
+
+        // Cookies set by the host are stored in the
+        // jar held by the pooled client, so it only
+        // needs rebuilding when our session differs
+        // from the one it was configured with.
+        let session_id = self.session_id();
+        if connection.client.is_none() || connection.session_id != session_id {
+            let client = self
+                .new_client_builder()?
+                .build()
+                .map(|c| {
+                    log::debug!("configured internal REST client");
+                    c
+                })
+                .map_err(|c| {
+                    log::error!("could not configure REST client");
+                    c
+                })?;
+            connection.client = Some(client);
+            connection.session_id = session_id;
+        }
+        Ok(connection.client.clone().unwrap())
     }
 
     fn configure(hostname: &str) -> XnatBuilder<Self::Version> {
@@ -123,9 +272,15 @@ impl<V: Version + Clone> ClientCore for Xnat<V> {
 
     fn new(base_url: &reqwest::Url, timeouts: &Option<Timeouts>, use_secure: bool, version: &Self::Version) -> Self {
         Self {
-            base_url:   base_url.to_owned(),
-            session_id: None,
-            timeouts:   timeouts.unwrap_or_default(),
+            base_url:     base_url.to_owned(),
+            capabilities: Arc::default(),
+            connection:   Arc::default(),
+            network:      Network::default(),
+            preflight:    Preflight::default(),
+            reauth:       Reauth::default(),
+            retry:        RetryPolicy::default(),
+            session_id:   Arc::default(),
+            timeouts:     timeouts.unwrap_or_default(),
             use_secure,
             version: version.to_owned(),
         }
@@ -153,119 +308,191 @@ where
     }
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 pub trait ClientREST: ClientCore {
+    /// Send a request to the host. Should the
+    /// host find the session has expired, and
+    /// this client retains credentials, a new
+    /// session is acquired and the request is
+    /// sent once more. Idempotent requests which
+    /// fail for transient reasons are retried
+    /// according to the client's `RetryPolicy`.
+    async fn execute(&self, request: reqwest::RequestBuilder) -> crate::Result<reqwest::Response>;
     /// Initialize a `DELETE` request. Is
     /// successful if the given URI endpoint
     /// supports `DELETE`.
-    async fn delete<UB: UriBuilder + ?Sized>(&self, uri: &UB) -> RequestBuilderResult;
+    async fn delete<UB: UriBuilder + Sync + ?Sized>(&self, uri: &UB) -> RequestBuilderResult;
     /// Initialize a `GET` request. Is successful
     /// if the given URI endpoint supports `GET`.
-    async fn get<UB: UriBuilder + ?Sized>(&self, uri: &UB) -> RequestBuilderResult;
+    async fn get<UB: UriBuilder + Sync + ?Sized>(&self, uri: &UB) -> RequestBuilderResult;
     /// Initialize a `HEAD` request. Is successful
     /// if the given URI endpoint supports `HEAD`.
-    async fn head<UB: UriBuilder + ?Sized>(&self, uri: &UB) -> RequestBuilderResult;
+    async fn head<UB: UriBuilder + Sync + ?Sized>(&self, uri: &UB) -> RequestBuilderResult;
     /// URI endpoint supports the REST method.
-    async fn method_is_supported<UB: UriBuilder + ?Sized>(&self, method: &Method, uri: &UB) -> anyhow::Result<bool>;
+    async fn method_is_supported<UB: UriBuilder + Sync + ?Sized>(&self, method: &Method, uri: &UB) -> crate::Result<bool>;
     /// Initialize a `OPTION` request.
-    async fn options<UB: UriBuilder + ?Sized>(&self, uri: &UB) -> RequestBuilderResult;
+    async fn options<UB: UriBuilder + Sync + ?Sized>(&self, uri: &UB) -> RequestBuilderResult;
     /// Initialize a `POST` request. Is successful
     /// if the given URI endpoint supports `POST`.
-    async fn post<UB: UriBuilder + ?Sized>(&self, uri: &UB) -> RequestBuilderResult;
+    async fn post<UB: UriBuilder + Sync + ?Sized>(&self, uri: &UB) -> RequestBuilderResult;
     /// Initialize a `PUT` request. Is successful
     /// if the given URI endpoint supports `PUT`.
-    async fn put<UB: UriBuilder + ?Sized>(&self, uri: &UB) -> RequestBuilderResult;
+    async fn put<UB: UriBuilder + Sync + ?Sized>(&self, uri: &UB) -> RequestBuilderResult;
     /// Pre-initializes a `RequestBuilder` for
     /// further configuration and sends to the
     /// host.
-    async fn request<UB: UriBuilder + ?Sized>(&self, method: Method, uri: &UB) -> RequestBuilderResult;
+    async fn request<UB: UriBuilder + Sync + ?Sized>(&self, method: Method, uri: &UB) -> RequestBuilderResult;
     /// Makes a request for some URI endpoint if
     /// the method is supported.
-    async fn request_if_supported<UB: UriBuilder + ?Sized>(&self, method: Method, uri: &UB) -> RequestBuilderResult;
+    async fn request_if_supported<UB: UriBuilder + Sync + ?Sized>(&self, method: Method, uri: &UB) -> RequestBuilderResult;
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 impl<V: Version + Clone> ClientREST for Xnat<V> {
-    async fn delete<UB: UriBuilder + ?Sized>(&self, uri: &UB) -> RequestBuilderResult {
+    async fn delete<UB: UriBuilder + Sync + ?Sized>(&self, uri: &UB) -> RequestBuilderResult {
         self.request_if_supported(Method::DELETE, uri).await
     }
 
-    async fn get<UB: UriBuilder + ?Sized>(&self, uri: &UB) -> RequestBuilderResult {
+    async fn execute(&self, request: reqwest::RequestBuilder) -> crate::Result<reqwest::Response> {
+        let session_id = self.session_id();
+        let (mut client, request) = request.build_split();
+        let mut request = request?;
+        let url = request.url().to_owned();
+        let method = request.method().to_owned();
+
+        // Only idempotent requests are safe to send
+        // again after the host may have acted on
+        // them.
+        let is_idempotent = request.method().is_idempotent();
+        let mut attempt = 1;
+        let mut renewed = false;
+        loop {
+            let next = request.try_clone();
+            let res = client.execute(request).await.map(|mut r| {
+                r.extensions_mut().insert(RequestMethod(method.to_owned()));
+                r
+            });
+            let Some(next) = next else {
+                return Ok(res?)
+            };
+
+            let delay = match &res {
+                Ok(r) if !renewed && self.session_needs_renewal(&url, r, &session_id).await? => {
+                    renewed = true;
+                    client = self.client()?;
+                    request = next;
+                    continue
+                },
+                Ok(r) if is_idempotent => self.retry.delay_after(attempt, r),
+                Err(e) if is_idempotent => self.retry.delay_after_error(attempt, e),
+                _ => None,
+            };
+            let Some(delay) = delay else {
+                return Ok(res?)
+            };
+
+            match &res {
+                Ok(r) => log::warn!("request to `{url}` failed ({}), retrying in {delay:?}", r.status()),
+                Err(e) => log::warn!("request to `{url}` failed ({e}), retrying in {delay:?}"),
+            }
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+            request = next;
+        }
+    }
+
+    async fn get<UB: UriBuilder + Sync + ?Sized>(&self, uri: &UB) -> RequestBuilderResult {
         self.request_if_supported(Method::GET, uri).await
     }
 
-    async fn head<UB: UriBuilder + ?Sized>(&self, uri: &UB) -> RequestBuilderResult {
+    async fn head<UB: UriBuilder + Sync + ?Sized>(&self, uri: &UB) -> RequestBuilderResult {
         self.request_if_supported(Method::HEAD, uri).await
     }
 
-    async fn method_is_supported<UB: UriBuilder + ?Sized>(&self, method: &Method, uri: &UB) -> anyhow::Result<bool> {
-        let res = self
-            .options(uri)
-            .await?
-            .send()
-            .await;
+    async fn method_is_supported<UB: UriBuilder + Sync + ?Sized>(&self, method: &Method, uri: &UB) -> crate::Result<bool> {
+        let mode = self.preflight.mode();
+        if mode == PreflightMode::Off {
+            return Ok(true)
+        }
+
+        // Only trust the cache when it says a method
+        // is supported. Otherwise, we defer to the
+        // host in case the template is shared with
+        // an endpoint that behaves differently.
+        let uri_path = uri.build()?;
+        if mode == PreflightMode::Cached {
+            let cached = self
+                .capabilities
+                .get(&uri_path, self.preflight.ttl())
+                .is_some_and(|m| m.contains(method));
+            if cached {
+                log::debug!("using cached support of {method} for `{uri}`");
+                return Ok(true)
+            }
+        }
+
+        let res = self.execute(self.options(uri).await?).await;
 
         log::debug!("checking if `{uri}` supports {method}");
-        let is_supported = |a: &HeaderValue| {
-            !a.is_empty() && a.to_str().unwrap().contains(method.as_str())
+        let allowed = |a: &HeaderValue| {
+            parse_allow(a.to_str().unwrap_or_default())
         };
         match res {
-            Ok(r) if r.status() == 401 => {
-                log::warn!("user not authorized to access `{uri}`: (401)");
-                Ok(false)
-            },
-            Ok(r) if r.status() == 404 => {
-                log::warn!("could not find `{uri}`: (404)");
-                Ok(false)
+            Ok(r) if matches!(r.status().as_u16(), 401 | 403) => {
+                log::warn!("user not authorized to access `{uri}`: ({})", r.status());
+                Err(Error::from_response(r).await)
             },
-            Ok(r) if r.status().is_client_error() => {
+            Ok(r) if r.status().is_client_error() && r.status() != 405 => {
                 log::warn!("check if method `{method}` supported for `{uri}` failed: ({})", r.status());
-                Ok(false)
+                Err(Error::from_response(r).await)
             },
             Ok(r) if r.status().is_server_error() => {
                 log::warn!("could not reach host at `{uri}`: ({})", r.status());
-                Ok(false)
+                Err(Error::from_response(r).await)
             },
-            Ok(r) => Ok(r.headers().get("Allow").is_some_and(is_supported)),
-            Err(_) => Ok(false)
+            Ok(r) => {
+                let methods = r.headers().get("Allow").map(allowed).unwrap_or_default();
+                let is_supported = methods.contains(method);
+                self.capabilities.insert(&uri_path, methods);
+                Ok(is_supported)
+            },
+            Err(e) => Err(e)
         }
     }
 
-    async fn options<UB: UriBuilder + ?Sized>(&self, uri: &UB) -> RequestBuilderResult {
+    async fn options<UB: UriBuilder + Sync + ?Sized>(&self, uri: &UB) -> RequestBuilderResult {
         self.request(Method::OPTIONS, uri).await
     }
 
-    async fn post<UB: UriBuilder + ?Sized>(&self, uri: &UB) -> RequestBuilderResult {
+    async fn post<UB: UriBuilder + Sync + ?Sized>(&self, uri: &UB) -> RequestBuilderResult {
         self.request_if_supported(Method::POST, uri).await
     }
 
-    async fn put<UB: UriBuilder + ?Sized>(&self, uri: &UB) -> RequestBuilderResult {
+    async fn put<UB: UriBuilder + Sync + ?Sized>(&self, uri: &UB) -> RequestBuilderResult {
         self.request_if_supported(Method::PUT, uri).await
     }
 
-    async fn request<UB: UriBuilder + ?Sized>(&self, method: Method, uri: &UB) -> RequestBuilderResult {
-        let mut url = self.base_url();
-        url.set_path(&uri.build()?);
-
+    async fn request<UB: UriBuilder + Sync + ?Sized>(&self, method: Method, uri: &UB) -> RequestBuilderResult {
+        let url = self.url_for(&uri.build()?);
         let builder = self
             .client()?
-            .request(method, url.to_owned());
+            .request(method, url);
         Ok(builder)
     }
 
-    async fn request_if_supported<UB: UriBuilder + ?Sized>(&self, method: Method, uri: &UB) -> RequestBuilderResult {
+    async fn request_if_supported<UB: UriBuilder + Sync + ?Sized>(&self, method: Method, uri: &UB) -> RequestBuilderResult {
         if self.method_is_supported(&method, uri).await? {
             log::debug!("method `{method}` supported for `{uri}`");
             self.request(method, uri).await
         } else {
-            Err(ClientError::UnsupportedMethod(method, uri.to_string()).into())
+            Err(Error::UnsupportedMethod(method, uri.to_string()))
         }
     }
 }
 
 /// Methods necessary for managing auth tokens
 /// from an XNAT host.
-#[async_trait(?Send)]
+#[async_trait]
 pub trait ClientToken: ClientCore {
     /// Acquire an auth token from the XNAT host.
     /// 
@@ -282,7 +509,7 @@ pub trait ClientToken: ClientCore {
     ///     .with_username("my-username")
     ///     .acquire().await?;
     /// ```
-    async fn acquire(&mut self) -> anyhow::Result<()>;
+    async fn acquire(&mut self) -> crate::Result<()>;
     /// Invalidates the auth token.
     /// 
     /// ```no_compile
@@ -300,64 +527,66 @@ pub trait ClientToken: ClientCore {
     /// 
     /// client.release().await?;
     /// ```
-    async fn release(&mut self) -> anyhow::Result<()>;
+    async fn release(&mut self) -> crate::Result<()>;
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 impl<V: Version + Clone> ClientToken for Xnat<V>
 where
     Self: ClientAuth,
 {
-    async fn acquire(&mut self) -> anyhow::Result<()> {
+    async fn acquire(&mut self) -> crate::Result<()> {
         let res = self
-            .post(&self.auth_uri()?)
-            .await?
-            .send()
+            .execute(self.post(&self.auth_uri()?).await?)
             .await?;
         tokacq_validator(res).await.map(|token| {
             self.set_session_id(&token);
         })
     }
 
-    async fn release(&mut self) -> anyhow::Result<()> {
+    async fn release(&mut self) -> crate::Result<()> {
         let res = self
-            .delete(&self.auth_uri()?)
-            .await?
-            .send()
+            .execute(self.delete(&self.auth_uri()?).await?)
             .await?;
         tokrel_validator(res).await.map(|r| {
-            self.session_id.take();
+            self.replace_session_id(None);
             r
         })
     }
 }
 
+/// Join a path onto the base URL of an XNAT
+/// host, appending to, rather than replacing, any
+/// path the base URL already has.
+pub fn join_url(base_url: &reqwest::Url, path: &str) -> reqwest::Url {
+    let mut url = base_url.to_owned();
+    let prefix = base_url.path().trim_end_matches('/');
+    url.set_path(&format!("{prefix}/{}", path.trim_start_matches('/')));
+    url
+}
+
 /// Helper function for token acquisition to
 /// validate that the transaction was successful.
-pub async fn tokacq_validator(res: reqwest::Response) -> anyhow::Result<String> {
+pub async fn tokacq_validator(res: reqwest::Response) -> crate::Result<String> {
     let status = res.status();
     log::debug!("request for auth token acquisition: {status}");
 
     if status.is_success() {
         Ok(res.text().await?)
-    } else if status.is_client_error() {
-        Err(ClientError::AuthFailure(status.as_u16()).into())
     } else {
-        Err(ClientError::ServerFailure(status.as_u16()).into())
+        Err(Error::from_response(res).await)
     }
 }
 
 /// Helper function for token relinquishment to
 /// validate that the transaction was successful.
-pub async fn tokrel_validator(res: reqwest::Response) -> anyhow::Result<()> {
+pub async fn tokrel_validator(res: reqwest::Response) -> crate::Result<()> {
     let status = res.status();
     log::debug!("request for auth token relinquisment: {status}");
 
     if status.is_success() {
         Ok(())
-    } else if status.is_client_error() {
-        Err(ClientError::DeauthFailure(status.as_u16()).into())
     } else {
-        Err(ClientError::ServerFailure(status.as_u16()).into())
+        Err(Error::from_response(res).await)
     }
 }
@@ -1,9 +1,16 @@
 pub mod builder;
 pub mod clients;
-pub mod error;
+pub mod network;
+pub mod preflight;
+pub mod retry;
+pub mod session;
 pub mod timeouts;
 
 pub static APP_CONNECT_TIMEOUT: u64 = 5;
+pub static APP_PREFLIGHT_TTL: u64 = 300;
+pub static APP_RETRY_ATTEMPTS: u32 = 3;
+pub static APP_RETRY_BASE_DELAY: u64 = 200;
+pub static APP_RETRY_MAX_DELAY: u64 = 30;
 static APP_USER_AGENT: &str = concat!(
     env!("CARGO_PKG_NAME"),
     "/",
@@ -23,3 +30,7 @@ pub use clients::{
     ClientToken,
     Xnat
 };
+pub use network::CertificateFormat;
+pub use preflight::PreflightMode;
+pub use retry::RetryPolicy;
+pub use session::{ReauthEvent, ReauthReason};
@@ -0,0 +1,167 @@
+use std::fmt::Debug;
+
+use reqwest::{Certificate, ClientBuilder, Identity, NoProxy, Proxy};
+
+/// Encoding of certificate data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CertificateFormat {
+    /// Base64 encoded, with `-----BEGIN ...`
+    /// armor.
+    Pem,
+    /// Binary DER encoded.
+    Der,
+}
+
+/// Constructor of a proxy for some scheme.
+type ProxyFor = fn(String) -> reqwest::Result<Proxy>;
+
+/// Certificate and private key a client presents
+/// to the host for mutual TLS.
+#[derive(Clone)]
+enum ClientIdentity {
+    /// PEM encoded certificate chain and PKCS#8
+    /// private key.
+    Pem(Vec<u8>, Vec<u8>),
+    /// DER encoded PKCS#12 archive and its
+    /// password.
+    Pkcs12(Vec<u8>, String),
+}
+
+/// TLS and proxy settings used to configure the
+/// connection between a client and the host.
+#[derive(Clone, Default)]
+pub struct Network {
+    identity:          Option<ClientIdentity>,
+    insecure:          bool,
+    no_proxy:          Option<String>,
+    proxy:             Option<String>,
+    root_certificates: Vec<(CertificateFormat, Vec<u8>)>,
+}
+
+impl Network {
+    /// Whether invalid certificates and host
+    /// names are accepted from the host.
+    pub fn insecure(&self) -> bool {
+        self.insecure
+    }
+
+    /// Apply these settings to a REST client
+    /// builder.
+    pub fn configure(&self, mut builder: ClientBuilder) -> crate::Result<ClientBuilder> {
+        for (format, data) in &self.root_certificates {
+            let certs = match format {
+                CertificateFormat::Pem => Certificate::from_pem_bundle(data)?,
+                CertificateFormat::Der => vec![Certificate::from_der(data)?],
+            };
+            for cert in certs {
+                builder = builder.add_root_certificate(cert);
+            }
+        }
+
+        if let Some(identity) = &self.identity {
+            builder = builder.identity(match identity {
+                ClientIdentity::Pem(cert, key) => Identity::from_pkcs8_pem(cert, key)?,
+                ClientIdentity::Pkcs12(der, password) => Identity::from_pkcs12_der(der, password)?,
+            });
+        }
+
+        let no_proxy = self.no_proxy.as_deref();
+        if no_proxy.is_some_and(|n| n.trim() == "*") {
+            return Ok(builder.no_proxy())
+        }
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(Proxy::all(proxy)?.no_proxy(no_proxy.and_then(NoProxy::from_string)));
+        } else if let Some(no_proxy) = no_proxy {
+            // Proxies from the environment are set up
+            // here instead, so that the hosts given
+            // bypass them along with `NO_PROXY`.
+            let no_proxy = match env_var("NO_PROXY") {
+                Some(env) => format!("{no_proxy},{env}"),
+                None => no_proxy.to_owned(),
+            };
+            builder = builder.no_proxy();
+            let proxies: [(&str, ProxyFor); 3] = [
+                ("HTTP_PROXY", Proxy::http),
+                ("HTTPS_PROXY", Proxy::https),
+                ("ALL_PROXY", Proxy::all),
+            ];
+            for (name, proxy) in proxies {
+                if let Some(url) = env_var(name) {
+                    builder = builder.proxy(proxy(url)?.no_proxy(NoProxy::from_string(&no_proxy)));
+                }
+            }
+        }
+        Ok(builder)
+    }
+
+    /// Set a client certificate chain and PKCS#8
+    /// private key, both PEM encoded, presented
+    /// to the host for mutual TLS.
+    pub fn with_identity_pem(mut self, cert: &[u8], key: &[u8]) -> Self {
+        self.identity = Some(ClientIdentity::Pem(cert.to_vec(), key.to_vec()));
+        self
+    }
+
+    /// Set a DER encoded PKCS#12 archive
+    /// presented to the host for mutual TLS.
+    pub fn with_identity_pkcs12(mut self, der: &[u8], password: &str) -> Self {
+        self.identity = Some(ClientIdentity::Pkcs12(der.to_vec(), password.to_owned()));
+        self
+    }
+
+    /// Set whether invalid certificates and host
+    /// names are accepted from the host. Unlike
+    /// disabling `use_secure`, HTTPS is still
+    /// required.
+    pub fn with_insecure(mut self, value: bool) -> Self {
+        self.insecure = value;
+        self
+    }
+
+    /// Set the hosts, in the same form as the
+    /// `NO_PROXY` environment variable, which
+    /// bypass the proxy, whether set by
+    /// `with_proxy` or by the environment. A
+    /// value of `*` disables all proxies.
+    pub fn with_no_proxy(mut self, hosts: &str) -> Self {
+        self.no_proxy.clone_from(&Some(hosts.to_owned()));
+        self
+    }
+
+    /// Set a proxy all requests are sent through.
+    /// Otherwise, proxies are taken from the
+    /// environment.
+    pub fn with_proxy(mut self, url: &str) -> Self {
+        self.proxy.clone_from(&Some(url.to_owned()));
+        self
+    }
+
+    /// Add a certificate authority trusted in
+    /// addition to the system roots. PEM data may
+    /// contain several certificates.
+    pub fn with_root_certificate(mut self, data: &[u8], format: CertificateFormat) -> Self {
+        self.root_certificates.push((format, data.to_vec()));
+        self
+    }
+}
+
+/// Value of an environment variable, by its
+/// upper or lower case name, if set.
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name)
+        .or_else(|_| std::env::var(name.to_lowercase()))
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+}
+
+impl Debug for Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Network")
+            .field("identity", &self.identity.as_ref().map(|_| "********"))
+            .field("insecure", &self.insecure)
+            .field("no_proxy", &self.no_proxy)
+            .field("proxy", &self.proxy)
+            .field("root_certificates", &self.root_certificates.len())
+            .finish()
+    }
+}
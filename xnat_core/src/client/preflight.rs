@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+use reqwest::Method;
+
+/// Path segments in XNAT URIs which are followed
+/// by an identifier, rather than another
+/// endpoint.
+static COLLECTIONS: &[&str] = &[
+    "assessors",
+    "experiments",
+    "projects",
+    "reconstructions",
+    "resources",
+    "scans",
+    "subjects",
+];
+
+/// Dictates whether a client checks that an
+/// endpoint supports a REST method, via an
+/// `OPTIONS` request, before making a request to
+/// that endpoint.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PreflightMode {
+    /// Never check the endpoint. Requests are
+    /// sent directly to the host.
+    Off,
+    /// Check the endpoint once and reuse the
+    /// result for all URIs sharing the same
+    /// template until it expires.
+    #[default]
+    Cached,
+    /// Check the endpoint before every request.
+    Always,
+}
+
+/// Periods of duration and the mode used to
+/// configure pre-flight checks made by a client.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Preflight {
+    mode: PreflightMode,
+    ttl:  Option<Duration>,
+}
+
+impl Preflight {
+    /// How pre-flight checks are made.
+    pub fn mode(&self) -> PreflightMode {
+        self.mode
+    }
+
+    /// How long a cached pre-flight check
+    /// remains valid.
+    pub fn ttl(&self) -> Duration {
+        self
+            .ttl
+            .unwrap_or(Duration::from_secs(super::APP_PREFLIGHT_TTL))
+    }
+
+    /// Set how pre-flight checks are made.
+    pub fn with_mode(mut self, mode: PreflightMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set how long a cached pre-flight check
+    /// remains valid.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl.clone_from(&Some(ttl));
+        self
+    }
+}
+
+/// Cache of the REST methods each URI template
+/// is known to support.
+#[derive(Debug, Default)]
+pub struct Capabilities {
+    entries: RwLock<HashMap<String, (Instant, Vec<Method>)>>,
+}
+
+impl Capabilities {
+    /// Drop all cached entries.
+    pub fn clear(&self) {
+        if let Ok(mut entries) = self.entries.write() {
+            entries.clear();
+        }
+    }
+
+    /// Get the methods supported by the URI,
+    /// if a fresh entry exists for its template.
+    pub fn get(&self, uri: &str, ttl: Duration) -> Option<Vec<Method>> {
+        let entries = self.entries.read().ok()?;
+        entries
+            .get(&uri_template(uri))
+            .filter(|(created, _)| created.elapsed() < ttl)
+            .map(|(_, methods)| methods.to_owned())
+    }
+
+    /// Record the methods supported by the
+    /// template of the given URI.
+    pub fn insert(&self, uri: &str, methods: Vec<Method>) {
+        if let Ok(mut entries) = self.entries.write() {
+            entries.insert(uri_template(uri), (Instant::now(), methods));
+        }
+    }
+}
+
+/// Parse the value of an `Allow` header into the
+/// methods it declares.
+pub fn parse_allow(value: &str) -> Vec<Method> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .filter_map(|m| Method::from_bytes(m.to_uppercase().as_bytes()).ok())
+        .collect()
+}
+
+/// Normalize a URI into the template it was
+/// built from, replacing identifiers with a
+/// placeholder.
+///
+/// e.g. `data/projects/SOME_PROJECT/subjects`
+/// becomes `data/projects/{}/subjects`.
+pub fn uri_template(uri: &str) -> String {
+    let path = uri
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default();
+
+    let mut template = vec![];
+    let mut segments = path.split('/').filter(|s| !s.is_empty());
+    while let Some(segment) = segments.next() {
+        template.push(segment.to_owned());
+        if segment == "files" {
+            // File paths may contain any number of
+            // segments.
+            if segments.next().is_some() {
+                template.push("{}".into());
+            }
+            break;
+        } else if COLLECTIONS.contains(&segment) && segments.next().is_some() {
+            template.push("{}".into());
+        }
+    }
+    template.join("/")
+}
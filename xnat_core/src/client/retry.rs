@@ -0,0 +1,181 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::io::ErrorKind;
+use std::time::Duration;
+
+use reqwest::{header::RETRY_AFTER, Response, StatusCode};
+
+/// Status codes retried by default. These are
+/// responses XNAT, or a proxy in front of it,
+/// gives when it is busy or restarting.
+static RETRY_STATUSES: &[StatusCode] = &[
+    StatusCode::REQUEST_TIMEOUT,
+    StatusCode::TOO_MANY_REQUESTS,
+    StatusCode::INTERNAL_SERVER_ERROR,
+    StatusCode::BAD_GATEWAY,
+    StatusCode::SERVICE_UNAVAILABLE,
+    StatusCode::GATEWAY_TIMEOUT,
+];
+
+/// Dictates how a client retries idempotent
+/// requests which fail for transient reasons.
+#[derive(Clone, Debug, Default)]
+pub struct RetryPolicy {
+    base_delay:   Option<Duration>,
+    jitter:       Option<bool>,
+    max_attempts: Option<u32>,
+    max_delay:    Option<Duration>,
+    statuses:     Option<Vec<StatusCode>>,
+}
+
+impl RetryPolicy {
+    /// Delay before the first retry. Following
+    /// retries double this delay.
+    pub fn base_delay(&self) -> Duration {
+        self
+            .base_delay
+            .unwrap_or(Duration::from_millis(super::APP_RETRY_BASE_DELAY))
+    }
+
+    /// Whether a random portion of each delay is
+    /// dropped, spreading out retries from
+    /// concurrent requests.
+    pub fn jitter(&self) -> bool {
+        self.jitter.unwrap_or(true)
+    }
+
+    /// Total number of times a request may be
+    /// sent, including the first.
+    pub fn max_attempts(&self) -> u32 {
+        self
+            .max_attempts
+            .unwrap_or(super::APP_RETRY_ATTEMPTS)
+            .max(1)
+    }
+
+    /// Upper limit to any one delay, including
+    /// those asked for by the host.
+    pub fn max_delay(&self) -> Duration {
+        self
+            .max_delay
+            .unwrap_or(Duration::from_secs(super::APP_RETRY_MAX_DELAY))
+    }
+
+    /// Status codes which cause a request to be
+    /// retried.
+    pub fn statuses(&self) -> &[StatusCode] {
+        self.statuses.as_deref().unwrap_or(RETRY_STATUSES)
+    }
+
+    /// How long to wait before sending the next
+    /// attempt, if the response warrants one.
+    ///
+    /// Honours the `Retry-After` header when the
+    /// host sends one.
+    pub fn delay_after(&self, attempt: u32, res: &Response) -> Option<Duration> {
+        if attempt >= self.max_attempts() || !self.statuses().contains(&res.status()) {
+            return None
+        }
+        let delay = retry_after(res).unwrap_or_else(|| self.backoff(attempt));
+        Some(delay.min(self.max_delay()))
+    }
+
+    /// How long to wait before sending the next
+    /// attempt, if the error warrants one.
+    ///
+    /// Only failures to connect, timeouts and
+    /// dropped connections are retried. Requests
+    /// which could not be built or sent for any
+    /// other reason would fail the same way again.
+    pub fn delay_after_error(&self, attempt: u32, error: &reqwest::Error) -> Option<Duration> {
+        let is_transient = error.is_connect() || error.is_timeout() || is_dropped(error);
+        if attempt >= self.max_attempts() || !is_transient {
+            return None
+        }
+        Some(self.backoff(attempt))
+    }
+
+    /// Set the delay before the first retry.
+    pub fn with_base_delay(mut self, value: Duration) -> Self {
+        self.base_delay.clone_from(&Some(value));
+        self
+    }
+
+    /// Set whether delays are randomized.
+    pub fn with_jitter(mut self, value: bool) -> Self {
+        self.jitter.clone_from(&Some(value));
+        self
+    }
+
+    /// Set the total number of times a request
+    /// may be sent. A value of `1` disables
+    /// retries.
+    pub fn with_max_attempts(mut self, value: u32) -> Self {
+        self.max_attempts.clone_from(&Some(value));
+        self
+    }
+
+    /// Set the upper limit to any one delay.
+    pub fn with_max_delay(mut self, value: Duration) -> Self {
+        self.max_delay.clone_from(&Some(value));
+        self
+    }
+
+    /// Set the status codes which cause a request
+    /// to be retried.
+    pub fn with_statuses(mut self, value: &[StatusCode]) -> Self {
+        self.statuses.clone_from(&Some(value.to_vec()));
+        self
+    }
+
+    /// Exponential delay for the given attempt,
+    /// keeping between half and all of it when
+    /// jitter is enabled.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let delay = self
+            .base_delay()
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.max_delay());
+        if !self.jitter() {
+            return delay
+        }
+
+        let half = delay / 2;
+        let nanos = half.as_nanos().min(u64::MAX.into()) as u64;
+        half + Duration::from_nanos(random() % nanos.max(1))
+    }
+}
+
+/// Whether the connection was dropped while the
+/// request was in flight.
+fn is_dropped(error: &reqwest::Error) -> bool {
+    let mut source = std::error::Error::source(error);
+    while let Some(e) = source {
+        if let Some(e) = e.downcast_ref::<std::io::Error>() {
+            return matches!(
+                e.kind(),
+                ErrorKind::ConnectionReset | ErrorKind::ConnectionAborted | ErrorKind::BrokenPipe | ErrorKind::UnexpectedEof
+            )
+        }
+        source = e.source();
+    }
+    false
+}
+
+/// Parse the `Retry-After` header as either a
+/// number of seconds or an HTTP date.
+fn retry_after(res: &Response) -> Option<Duration> {
+    let value = res.headers().get(RETRY_AFTER)?.to_str().ok()?;
+    if let Ok(secs) = value.trim().parse::<u64>() {
+        return Some(Duration::from_secs(secs))
+    }
+
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    (date.with_timezone(&chrono::Utc) - chrono::Utc::now()).to_std().ok()
+}
+
+/// Random value seeded by the standard library's
+/// per-instance hasher keys.
+fn random() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
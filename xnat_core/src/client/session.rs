@@ -0,0 +1,145 @@
+use std::fmt::{Debug, Display};
+use std::sync::Arc;
+
+use reqwest::{Response, StatusCode};
+
+/// Callback invoked after a client has renewed an
+/// expired session.
+pub type ReauthHook = Arc<dyn Fn(&ReauthEvent) + Send + Sync>;
+
+/// Username and password retained by a client so
+/// that it may acquire a new session from the
+/// host.
+#[derive(Clone)]
+pub struct Credentials {
+    username: String,
+    password: Option<String>,
+}
+
+impl Credentials {
+    /// Create a new set of credentials.
+    pub fn new(username: &str, password: Option<&str>) -> Self {
+        Self {
+            username: username.to_owned(),
+            password: password.map(str::to_owned),
+        }
+    }
+
+    /// The auth password.
+    pub fn password(&self) -> Option<&str> {
+        self.password.as_deref()
+    }
+
+    /// The auth username.
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+}
+
+impl Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "********"))
+            .finish()
+    }
+}
+
+/// Why a response was determined to come from an
+/// expired session.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReauthReason {
+    /// The host responded with `401 Unauthorized`.
+    Unauthorized,
+    /// The host redirected the request to its
+    /// login page.
+    LoginRedirect,
+}
+
+impl Display for ReauthReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unauthorized  => write!(f, "unauthorized"),
+            Self::LoginRedirect => write!(f, "redirected to login"),
+        }
+    }
+}
+
+/// Details of a session renewal, passed to a
+/// `ReauthHook`.
+#[derive(Clone, Debug)]
+pub struct ReauthEvent {
+    /// The URL of the request which found the
+    /// session to be expired.
+    pub url: reqwest::Url,
+    /// Why the session was determined to be
+    /// expired.
+    pub reason: ReauthReason,
+}
+
+/// Settings used by a client to renew an expired
+/// session.
+#[derive(Clone, Default)]
+pub struct Reauth {
+    credentials: Option<Credentials>,
+    hook:        Option<ReauthHook>,
+}
+
+impl Reauth {
+    /// The credentials retained for session
+    /// renewal, if any.
+    pub fn credentials(&self) -> Option<&Credentials> {
+        self.credentials.as_ref()
+    }
+
+    /// Call the hook, if one is set, with the
+    /// given event.
+    pub fn notify(&self, event: &ReauthEvent) {
+        if let Some(hook) = &self.hook {
+            hook(event)
+        }
+    }
+
+    /// Set the credentials retained for session
+    /// renewal.
+    pub fn with_credentials(mut self, credentials: Option<Credentials>) -> Self {
+        self.credentials = credentials;
+        self
+    }
+
+    /// Set the callback invoked after a session
+    /// is renewed.
+    pub fn with_hook(mut self, hook: Option<ReauthHook>) -> Self {
+        self.hook = hook;
+        self
+    }
+}
+
+impl Debug for Reauth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Reauth")
+            .field("credentials", &self.credentials)
+            .field("hook", &self.hook.as_ref().map(|_| "Fn(&ReauthEvent)"))
+            .finish()
+    }
+}
+
+/// Determine whether the response indicates the
+/// session used to make the request to the given
+/// URL has expired.
+pub fn session_expired(requested: &reqwest::Url, res: &Response) -> Option<ReauthReason> {
+    if res.status() == StatusCode::UNAUTHORIZED {
+        return Some(ReauthReason::Unauthorized)
+    }
+
+    // XNAT sends unauthenticated browser-like
+    // requests to its login page, which reqwest
+    // follows transparently.
+    let path = res.url().path().to_lowercase();
+    let is_login = path.ends_with("login.vm") || path.ends_with("/login");
+    if is_login && res.url() != requested {
+        Some(ReauthReason::LoginRedirect)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,285 @@
+use std::fmt::Display;
+
+use reqwest::{header::CONTENT_TYPE, Method, Response, StatusCode, Url};
+use thiserror::Error;
+
+use crate::UriBuildError;
+
+/// Longest message, in characters, kept from the
+/// body of an error response.
+static MESSAGE_LIMIT: usize = 500;
+
+/// Result type returned by clients and protocols.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The method a response was requested with.
+/// Attached to responses by `ClientREST::execute`
+/// so errors can report it.
+#[derive(Clone, Debug)]
+pub struct RequestMethod(pub Method);
+
+/// Details of a request the host responded to
+/// with an error status.
+#[derive(Clone, Debug)]
+pub struct ErrorResponse {
+    /// Method of the failed request.
+    pub method: Option<Method>,
+    /// Full URL of the failed request.
+    pub url: Option<Url>,
+    /// Status the host responded with.
+    pub status: StatusCode,
+    /// Explanation decoded from the response
+    /// body, if the host gave one.
+    pub message: Option<String>,
+}
+
+impl ErrorResponse {
+    /// An error response known only by its
+    /// status.
+    pub fn from_status(status: StatusCode) -> Self {
+        Self { method: None, url: None, status, message: None }
+    }
+}
+
+impl Display for ErrorResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(method) = &self.method {
+            write!(f, "{method} ")?;
+        }
+        if let Some(url) = &self.url {
+            write!(f, "`{url}` ")?;
+        }
+        write!(f, "({})", self.status)?;
+        if let Some(message) = &self.message {
+            write!(f, ": {message}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Errors which occur during interactions
+/// between an XNAT client and the host.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("could not read zip archive: {0}")]
+    Archive(#[from] zip::result::ZipError),
+    #[error("not authenticated, {0}")]
+    Auth(Box<ErrorResponse>),
+    #[error("checksum of `{}` is {actual}, expected {expected}", .path.display())]
+    Checksum {
+        path: std::path::PathBuf,
+        expected: String,
+        actual: String,
+    },
+    #[error("invalid client configuration: {0}")]
+    Config(String),
+    #[error("resource already exists or is in use, {0}")]
+    Conflict(Box<ErrorResponse>),
+    #[error("could not deserialize response from `{}`: {source}", .url.as_ref().map(Url::as_str).unwrap_or_default())]
+    Deserialization {
+        url: Option<Url>,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    #[error("host XNAT experienced an internal error, {0}")]
+    Host(Box<ErrorResponse>),
+    #[error("operation requires `{0}`")]
+    IdentifierRequired(String),
+    #[error("could not read or write local file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("resource is not available, {0}")]
+    NotFound(Box<ErrorResponse>),
+    #[error("permission denied, {0}")]
+    Permission(Box<ErrorResponse>),
+    #[error("request rejected, {0}")]
+    Rejected(Box<ErrorResponse>),
+    #[error("timed out waiting for {0}")]
+    Timeout(String),
+    #[error("could not reach host: {0}")]
+    Transport(#[source] reqwest::Error),
+    #[error("refusing to write `{}` outside of the destination", .0.display())]
+    UnsafePath(std::path::PathBuf),
+    #[error("`{1}` does not support method `{0}`")]
+    UnsupportedMethod(Method, String),
+    #[error(transparent)]
+    UriBuild(#[from] UriBuildError),
+}
+
+impl Error {
+    /// Construct an error from a response with an
+    /// error status, decoding the explanation
+    /// from its body.
+    pub async fn from_response(res: Response) -> Self {
+        let method = res.extensions().get::<RequestMethod>().map(|m| m.0.to_owned());
+        let url = Some(res.url().to_owned());
+        let status = res.status();
+        let content_type = res
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|c| c.to_str().ok())
+            .unwrap_or_default()
+            .to_owned();
+        let message = res
+            .text()
+            .await
+            .ok()
+            .and_then(|body| decode_message(&content_type, &body));
+        Self::from_status(ErrorResponse { method, url, status, message })
+    }
+
+    /// Categorize an error response by its
+    /// status.
+    pub fn from_status(response: ErrorResponse) -> Self {
+        let response = Box::new(response);
+        match response.status {
+            StatusCode::UNAUTHORIZED => Self::Auth(response),
+            StatusCode::FORBIDDEN    => Self::Permission(response),
+            StatusCode::NOT_FOUND    => Self::NotFound(response),
+            StatusCode::CONFLICT     => Self::Conflict(response),
+            s if s.is_server_error() => Self::Host(response),
+            _ => Self::Rejected(response),
+        }
+    }
+
+    /// The details of the failed request, if the
+    /// host responded to it.
+    pub fn response(&self) -> Option<&ErrorResponse> {
+        match self {
+            Self::Auth(r)
+            | Self::Conflict(r)
+            | Self::Host(r)
+            | Self::NotFound(r)
+            | Self::Permission(r)
+            | Self::Rejected(r) => Some(r),
+            _ => None,
+        }
+    }
+
+    /// Status the host responded with, if any.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::Transport(e) => e.status(),
+            _ => self.response().map(|r| r.status),
+        }
+    }
+
+    /// Explanation given by the host, if any.
+    pub fn message(&self) -> Option<&str> {
+        self.response().and_then(|r| r.message.as_deref())
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(value: reqwest::Error) -> Self {
+        if value.is_decode() {
+            Self::Deserialization {
+                url: value.url().cloned(),
+                source: value.into(),
+            }
+        } else if value.is_builder() {
+            Self::Config(value.to_string())
+        } else {
+            Self::Transport(value)
+        }
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Deserialization { url: None, source: value.into() }
+    }
+}
+
+/// Decode the explanation for an error from a
+/// response body. XNAT responds with plain text,
+/// JSON or Restlet/Tomcat HTML error pages
+/// depending on where the error was raised.
+fn decode_message(content_type: &str, body: &str) -> Option<String> {
+    let body = body.trim();
+    let message = if content_type.contains("json") {
+        decode_json(body).unwrap_or_else(|| body.to_owned())
+    } else if content_type.contains("html") || body.starts_with('<') {
+        decode_html(body)
+    } else {
+        body.to_owned()
+    };
+
+    let message = collapse_whitespace(&message);
+    if message.is_empty() {
+        None
+    } else {
+        Some(message.chars().take(MESSAGE_LIMIT).collect())
+    }
+}
+
+/// Pick the message out of a JSON error body.
+fn decode_json(body: &str) -> Option<String> {
+    let value = serde_json::from_str::<serde_json::Value>(body).ok()?;
+    ["message", "errorMessage", "error"]
+        .iter()
+        .find_map(|k| value.get(k))
+        .map(|v| match v {
+            serde_json::Value::String(s) => s.to_owned(),
+            _ => v.to_string(),
+        })
+}
+
+/// Pick the message out of an HTML error page.
+///
+/// Tomcat pages state the message in a
+/// paragraph labelled `Message`. Restlet pages,
+/// and Tomcat pages without a message, state the
+/// reason in the first heading.
+fn decode_html(body: &str) -> String {
+    let lower = body.to_ascii_lowercase();
+    if let Some(start) = lower.find("<b>message</b>") {
+        let rest = &body[start + "<b>message</b>".len()..];
+        let end = rest.to_ascii_lowercase().find("</p>").unwrap_or(rest.len());
+        let message = strip_tags(&rest[..end]);
+        if !message.trim().is_empty() {
+            return message
+        }
+    }
+
+    let heading = ["<h1", "<h2", "<h3"]
+        .iter()
+        .filter_map(|h| lower.find(h))
+        .min();
+    for start in heading.into_iter().chain(lower.find("<title")) {
+        let rest = &body[start..];
+        let end = rest.find("</").unwrap_or(rest.len());
+        let message = strip_tags(&rest[..end]);
+        if !message.trim().is_empty() {
+            return message
+        }
+    }
+    strip_tags(body)
+}
+
+/// Remove markup and decode common entities.
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            },
+            _ if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+    text
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
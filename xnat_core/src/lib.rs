@@ -1,13 +1,18 @@
+#[cfg(feature = "blocking")]
+pub mod blocking;
+#[cfg(feature = "events-listener")]
+pub mod listener;
 pub mod protocols;
 pub mod client;
+pub mod error;
 pub mod models;
 pub mod uri;
 pub mod version;
 
-pub use anyhow;
 pub use oxinat_derive::*;
 
 pub use crate::client::{
+    CertificateFormat,
     ClientAuth,
     ClientCore,
     ClientREST,
@@ -15,9 +20,12 @@ pub use crate::client::{
     ClientBuilderAttrs,
     ClientBuilderCore,
     ClientBuilderToken,
+    PreflightMode,
+    RetryPolicy,
     Xnat,
     XnatBuilder,
 };
+pub use crate::error::{Error, ErrorResponse, Result};
 pub use crate::uri::{UriBuilder, UriBuildError, BuildResult};
 pub use crate::uri::admin::{
     AdminUri,
@@ -0,0 +1,512 @@
+//! Receives the events XNAT delivers to webhook
+//! subscriptions, for programs which react to
+//! changes on the host rather than polling it.
+//!
+//! An `EventListener` runs a small HTTP server.
+//! Each delivery posted to it is parsed into an
+//! `XnatEvent` and passed to every handler
+//! registered for its kind.
+//!
+//! ```no_compile
+//! let listener = EventListener::new()
+//!     .with_public_url("http://worker.internal:8080/events")
+//!     .with_handler_for(EventKind::Session, |event| println!("{event:?}"))
+//!     .bind("0.0.0.0:8080".parse()?)
+//!     .await?;
+//!
+//! let filter = EventFilter::new("org.nrg.xnat.eventservice.events.SessionEvent", "CREATED");
+//! listener.subscribe(&client, Some("PROJECT_A"), "Notify worker", filter).await?;
+//! ```
+use std::fmt::Debug;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::Bytes;
+use http_body_util::{BodyExt, Full, LengthLimitError, Limited};
+use hyper::body::Incoming;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::net::TcpListener;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::models::{EventFilter, EventSubscription};
+use crate::protocols::EventSubscriptions;
+use crate::Error;
+
+/// Key of the action XNAT uses to post events to
+/// a URL. Subscriptions created by
+/// `ListenerHandle::subscribe` use it unless told
+/// otherwise.
+pub const WEBHOOK_ACTION_KEY: &str =
+    "org.nrg.xnat.eventservice.actions.EventServiceWebhookAction:EventServiceWebhookAction";
+
+/// Largest delivery accepted by default, in
+/// bytes.
+pub const DEFAULT_BODY_LIMIT: usize = 1024 * 1024;
+
+/// Pause after failing to accept a connection,
+/// such as when out of file descriptors, before
+/// trying again.
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+
+/// A delivery XNAT posted to the listener, with
+/// its payload of type `T`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Event<T> {
+    /// Fully qualified type of the event, such as
+    /// `org.nrg.xnat.eventservice.events.SessionEvent`.
+    #[serde(rename = "event-type", alias = "eventType")]
+    pub event_type: String,
+    /// Status the event was reported with, such
+    /// as `CREATED`.
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub project: Option<String>,
+    /// Username of the account which caused the
+    /// event.
+    #[serde(default)]
+    pub user: Option<String>,
+    #[serde(rename = "subscription-id", alias = "subscriptionId", default)]
+    pub subscription_id: Option<u64>,
+    /// When the event happened, in milliseconds
+    /// since the epoch.
+    #[serde(default)]
+    pub timestamp: Option<i64>,
+    pub payload: T,
+}
+
+impl Event<Value> {
+    /// Parse the payload as some type `T`.
+    fn typed<T: DeserializeOwned>(&self) -> serde_json::Result<Event<T>> {
+        Ok(Event {
+            event_type: self.event_type.to_owned(),
+            status: self.status.to_owned(),
+            project: self.project.to_owned(),
+            user: self.user.to_owned(),
+            subscription_id: self.subscription_id,
+            timestamp: self.timestamp,
+            payload: serde_json::from_value(self.payload.to_owned())?,
+        })
+    }
+}
+
+/// The project an event is about.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ProjectPayload {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub uri: Option<String>,
+}
+
+/// The subject an event is about.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct SubjectPayload {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(rename = "project-id", alias = "projectId", default)]
+    pub project: Option<String>,
+    #[serde(default)]
+    pub uri: Option<String>,
+}
+
+/// The image session an event is about.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct SessionPayload {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(rename = "project-id", alias = "projectId", default)]
+    pub project: Option<String>,
+    #[serde(rename = "subject-id", alias = "subjectId", default)]
+    pub subject_id: Option<String>,
+    #[serde(rename = "xsi-type", alias = "xsiType", default)]
+    pub xsi_type: Option<String>,
+    #[serde(default)]
+    pub modality: Option<String>,
+    #[serde(default)]
+    pub uri: Option<String>,
+}
+
+/// The scan an event is about.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ScanPayload {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(rename = "project-id", alias = "projectId", default)]
+    pub project: Option<String>,
+    #[serde(rename = "session-id", alias = "sessionId", default)]
+    pub session_id: Option<String>,
+    #[serde(rename = "xsi-type", alias = "xsiType", default)]
+    pub xsi_type: Option<String>,
+    #[serde(rename = "series-description", alias = "seriesDescription", default)]
+    pub series_description: Option<String>,
+    #[serde(default)]
+    pub uri: Option<String>,
+}
+
+/// The workflow, such as a pipeline or container
+/// launch, an event is about.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct WorkflowPayload {
+    #[serde(rename = "wrk-workflow-data-id", alias = "workflowId", default)]
+    pub id: Option<u64>,
+    #[serde(rename = "pipeline-name", alias = "pipelineName", default)]
+    pub pipeline_name: Option<String>,
+    /// Status of the workflow, such as `Complete`
+    /// or `Failed`.
+    #[serde(default)]
+    pub status: Option<String>,
+    /// Id of the item the workflow ran on.
+    #[serde(rename = "item-id", alias = "itemId", default)]
+    pub item_id: Option<String>,
+    #[serde(rename = "data-type", alias = "dataType", default)]
+    pub data_type: Option<String>,
+    #[serde(default)]
+    pub details: Option<String>,
+}
+
+/// Which kind of event a delivery carries.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    Project,
+    Subject,
+    Session,
+    Scan,
+    WorkflowStatus,
+    /// An event of a type this crate does not
+    /// know of.
+    Other,
+}
+
+impl EventKind {
+    /// The kind of event named by a fully
+    /// qualified event type.
+    pub fn from_event_type(event_type: &str) -> Self {
+        match event_type.rsplit('.').next().unwrap_or_default() {
+            "ProjectEvent" => Self::Project,
+            "SubjectEvent" => Self::Subject,
+            "SessionEvent" | "ImageSessionEvent" => Self::Session,
+            "ScanEvent" => Self::Scan,
+            "WorkflowStatusEvent" => Self::WorkflowStatus,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// An event XNAT delivered to the listener.
+#[derive(Clone, Debug)]
+pub enum XnatEvent {
+    Project(Event<ProjectPayload>),
+    Subject(Event<SubjectPayload>),
+    Session(Event<SessionPayload>),
+    Scan(Event<ScanPayload>),
+    WorkflowStatus(Event<WorkflowPayload>),
+    /// An event of a type this crate does not
+    /// know of, or whose payload did not have
+    /// the expected shape.
+    Other(Event<Value>),
+}
+
+impl XnatEvent {
+    /// Parse a delivery posted by XNAT.
+    pub fn from_slice(body: &[u8]) -> crate::Result<Self> {
+        let event = serde_json::from_slice::<Event<Value>>(body)
+            .map_err(|e| Error::Deserialization { url: None, source: e.into() })?;
+
+        let typed = match EventKind::from_event_type(&event.event_type) {
+            EventKind::Project => event.typed().map(Self::Project),
+            EventKind::Subject => event.typed().map(Self::Subject),
+            EventKind::Session => event.typed().map(Self::Session),
+            EventKind::Scan => event.typed().map(Self::Scan),
+            EventKind::WorkflowStatus => event.typed().map(Self::WorkflowStatus),
+            EventKind::Other => return Ok(Self::Other(event)),
+        };
+        Ok(typed.unwrap_or_else(|e| {
+            log::warn!("unexpected payload for `{}`: {e}", event.event_type);
+            Self::Other(event)
+        }))
+    }
+
+    /// Which kind of event this is.
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Self::Project(_) => EventKind::Project,
+            Self::Subject(_) => EventKind::Subject,
+            Self::Session(_) => EventKind::Session,
+            Self::Scan(_) => EventKind::Scan,
+            Self::WorkflowStatus(_) => EventKind::WorkflowStatus,
+            Self::Other(_) => EventKind::Other,
+        }
+    }
+}
+
+/// Callback invoked with each event the listener
+/// receives.
+pub type EventHandler = Arc<dyn Fn(&XnatEvent) + Send + Sync>;
+
+/// Handlers registered with a listener, and the
+/// kind of event each is for. Handlers without a
+/// kind receive every event.
+#[derive(Clone, Default)]
+struct Handlers(Vec<(Option<EventKind>, EventHandler)>);
+
+impl Handlers {
+    fn dispatch(&self, event: &XnatEvent) {
+        let kind = event.kind();
+        self.0
+            .iter()
+            .filter(|(k, _)| k.is_none_or(|k| k == kind))
+            .for_each(|(_, handler)| handler(event));
+    }
+}
+
+/// A building pattern type for an HTTP server
+/// which receives events XNAT delivers to
+/// webhook subscriptions.
+#[derive(Clone, Default)]
+pub struct EventListener {
+    body_limit: Option<usize>,
+    handlers:   Handlers,
+    path:       Option<String>,
+    public_url: Option<String>,
+}
+
+impl EventListener {
+    /// Initialize a listener without any
+    /// handlers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a handler invoked with every
+    /// event received.
+    pub fn with_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&XnatEvent) + Send + Sync + 'static,
+    {
+        self.handlers.0.push((None, Arc::new(handler)));
+        self
+    }
+
+    /// Register a handler invoked only with events
+    /// of one kind.
+    pub fn with_handler_for<F>(mut self, kind: EventKind, handler: F) -> Self
+    where
+        F: Fn(&XnatEvent) + Send + Sync + 'static,
+    {
+        self.handlers.0.push((Some(kind), Arc::new(handler)));
+        self
+    }
+
+    /// Set the largest delivery accepted, in
+    /// bytes. Larger deliveries are refused with
+    /// `413 Payload Too Large`. Defaults to
+    /// `DEFAULT_BODY_LIMIT`.
+    pub fn with_body_limit(mut self, bytes: usize) -> Self {
+        self.body_limit = Some(bytes);
+        self
+    }
+
+    /// Set the path deliveries are accepted on.
+    /// Defaults to `/`.
+    pub fn with_path(mut self, path: &str) -> Self {
+        self.path = Some(format!("/{}", path.trim_start_matches('/')));
+        self
+    }
+
+    /// Set the URL XNAT reaches the listener at,
+    /// when it differs from the address the
+    /// listener is bound to, such as behind a
+    /// proxy.
+    pub fn with_public_url(mut self, url: &str) -> Self {
+        self.public_url = Some(url.to_owned());
+        self
+    }
+
+    /// Start accepting deliveries on `addr`. A
+    /// port of `0` binds any free port.
+    pub async fn bind(self, addr: SocketAddr) -> crate::Result<ListenerHandle> {
+        let socket = TcpListener::bind(addr)
+            .await
+            .map_err(|e| Error::Config(format!("cannot bind event listener to `{addr}`: {e}")))?;
+        let local_addr = socket
+            .local_addr()
+            .map_err(|e| Error::Config(format!("cannot bind event listener to `{addr}`: {e}")))?;
+
+        let path = self.path.unwrap_or_else(|| "/".to_owned());
+        let url = self
+            .public_url
+            .unwrap_or_else(|| format!("http://{local_addr}{path}"));
+        let (shutdown, signal) = watch::channel(false);
+        let server = Server {
+            body_limit: self.body_limit.unwrap_or(DEFAULT_BODY_LIMIT),
+            handlers: Arc::new(self.handlers),
+            path: Arc::from(path),
+        };
+        let task = tokio::spawn(server.run(socket, signal));
+
+        Ok(ListenerHandle { local_addr, shutdown, task, url })
+    }
+}
+
+impl Debug for EventListener {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kinds = self.handlers.0.iter().map(|(k, _)| k).collect::<Vec<_>>();
+        f.debug_struct("EventListener")
+            .field("body_limit", &self.body_limit)
+            .field("handlers", &kinds)
+            .field("path", &self.path)
+            .field("public_url", &self.public_url)
+            .finish()
+    }
+}
+
+/// Accepts connections and serves deliveries
+/// until told to shut down.
+struct Server {
+    body_limit: usize,
+    handlers:   Arc<Handlers>,
+    path:       Arc<str>,
+}
+
+impl Server {
+    async fn run(self, socket: TcpListener, mut signal: watch::Receiver<bool>) {
+        loop {
+            let stream = tokio::select! {
+                accepted = socket.accept() => match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        log::warn!("event listener failed to accept connection: {e}");
+                        tokio::time::sleep(ACCEPT_BACKOFF).await;
+                        continue
+                    },
+                },
+                // Only an explicit shutdown stops the
+                // listener, not dropping its handle.
+                Ok(()) = signal.changed() => return,
+            };
+
+            let body_limit = self.body_limit;
+            let handlers = self.handlers.clone();
+            let path = self.path.clone();
+            tokio::spawn(async move {
+                let service = service_fn(move |request| {
+                    let handlers = handlers.clone();
+                    let path = path.clone();
+                    async move {
+                        Ok::<_, hyper::Error>(receive(request, &handlers, &path, body_limit).await)
+                    }
+                });
+                if let Err(e) = http1::Builder::new().serve_connection(TokioIo::new(stream), service).await {
+                    log::warn!("event listener connection failed: {e}");
+                }
+            });
+        }
+    }
+}
+
+/// Answer a single request made of the listener.
+async fn receive(request: Request<Incoming>, handlers: &Handlers, path: &str, body_limit: usize) -> Response<Full<Bytes>> {
+    if request.uri().path() != path {
+        return respond(StatusCode::NOT_FOUND, "")
+    }
+    if request.method() != Method::POST {
+        return respond(StatusCode::METHOD_NOT_ALLOWED, "")
+    }
+
+    let body = match Limited::new(request.into_body(), body_limit).collect().await {
+        Ok(body) => body.to_bytes(),
+        Err(e) if e.is::<LengthLimitError>() => {
+            log::warn!("event listener refused delivery over {body_limit} bytes");
+            return respond(StatusCode::PAYLOAD_TOO_LARGE, &e.to_string())
+        },
+        Err(e) => return respond(StatusCode::BAD_REQUEST, &e.to_string()),
+    };
+    match XnatEvent::from_slice(&body) {
+        Ok(event) => {
+            handlers.dispatch(&event);
+            respond(StatusCode::OK, "")
+        },
+        Err(e) => {
+            log::warn!("event listener received malformed delivery: {e}");
+            respond(StatusCode::BAD_REQUEST, &e.to_string())
+        },
+    }
+}
+
+fn respond(status: StatusCode, body: &str) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from(body.to_owned())));
+    *response.status_mut() = status;
+    response
+}
+
+/// A running event listener. Dropping the handle
+/// leaves the listener running; call `shutdown`
+/// to stop it.
+#[derive(Debug)]
+pub struct ListenerHandle {
+    local_addr: SocketAddr,
+    shutdown:   watch::Sender<bool>,
+    task:       JoinHandle<()>,
+    url:        String,
+}
+
+impl ListenerHandle {
+    /// Address the listener is bound to.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// URL XNAT should deliver events to.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Create a subscription which delivers events
+    /// matching `filter` to this listener. Returns
+    /// the subscription as XNAT stored it.
+    pub async fn subscribe<C>(&self, client: &C, project: Option<&str>, name: &str, filter: EventFilter) -> crate::Result<EventSubscription>
+    where
+        C: EventSubscriptions + Sync,
+    {
+        let subscription = EventSubscription::new(name, WEBHOOK_ACTION_KEY, filter);
+        self.subscribe_with(client, project, subscription).await
+    }
+
+    /// Create a subscription which delivers events
+    /// to this listener, using any action given by
+    /// `subscription` in place of
+    /// `WEBHOOK_ACTION_KEY`.
+    pub async fn subscribe_with<C>(&self, client: &C, project: Option<&str>, mut subscription: EventSubscription) -> crate::Result<EventSubscription>
+    where
+        C: EventSubscriptions + Sync,
+    {
+        subscription.action_key.get_or_insert_with(|| WEBHOOK_ACTION_KEY.to_owned());
+        subscription
+            .attributes
+            .insert("url".to_owned(), Value::String(self.url.to_owned()));
+        client.create_subscription(project, &subscription).await
+    }
+
+    /// Stop accepting deliveries. Deliveries
+    /// already being handled are allowed to
+    /// finish.
+    pub async fn shutdown(self) {
+        let _ = self.shutdown.send(true);
+        let _ = self.task.await;
+    }
+}
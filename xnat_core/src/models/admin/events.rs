@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Narrows the events which trigger a
+/// subscription.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct EventFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Fully qualified type of the event, such as
+    /// `org.nrg.xnat.eventservice.events.SessionEvent`.
+    #[serde(rename = "event-type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_type: Option<String>,
+    /// Projects the event must come from. Empty
+    /// for events from any project.
+    #[serde(rename = "project-ids", default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub project_ids: Vec<String>,
+    /// Status the event must have, such as
+    /// `CREATED`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// JSONPath expression the event payload
+    /// must match.
+    #[serde(rename = "payload-filter")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload_filter: Option<String>,
+}
+
+impl EventFilter {
+    /// A filter on the type and status of events.
+    pub fn new(event_type: &str, status: &str) -> Self {
+        Self {
+            event_type: Some(event_type.to_owned()),
+            status: Some(status.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    /// Narrow the filter to events from the given
+    /// projects.
+    pub fn with_projects(mut self, projects: &[&str]) -> Self {
+        self.project_ids = projects.iter().map(|p| p.to_string()).collect();
+        self
+    }
+}
+
+/// Carries out an action whenever an event
+/// matches its filter.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct EventSubscription {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<bool>,
+    /// Key of the action carried out, as listed
+    /// by the actions available.
+    #[serde(rename = "action-key")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action_key: Option<String>,
+    /// Inputs given to the action.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub attributes: HashMap<String, Value>,
+    #[serde(rename = "event-filter")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_filter: Option<EventFilter>,
+    /// Whether the action is carried out as the
+    /// user who caused the event, rather than the
+    /// owner of the subscription.
+    #[serde(rename = "act-as-event-user")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub act_as_event_user: Option<bool>,
+
+    // Read-only attributes reported by XNAT.
+    #[serde(rename = "subscriber-user-id", skip_serializing)]
+    subscriber: Option<String>,
+    #[serde(skip_serializing)]
+    valid: Option<bool>,
+    #[serde(rename = "validation-message", skip_serializing)]
+    validation_message: Option<String>,
+}
+
+impl EventSubscription {
+    /// A subscription carrying out the action
+    /// known by `action_key` on events matching
+    /// `filter`.
+    pub fn new(name: &str, action_key: &str, filter: EventFilter) -> Self {
+        Self {
+            name: Some(name.to_owned()),
+            action_key: Some(action_key.to_owned()),
+            event_filter: Some(filter),
+            ..Default::default()
+        }
+    }
+
+    /// Username of the owner of the subscription.
+    pub fn subscriber(&self) -> &Option<String> {
+        &self.subscriber
+    }
+
+    /// Whether XNAT found the subscription valid
+    /// when it was last checked.
+    pub fn is_valid(&self) -> Option<bool> {
+        self.valid
+    }
+
+    /// Why XNAT found the subscription invalid, if
+    /// it did.
+    pub fn validation_message(&self) -> &Option<String> {
+        &self.validation_message
+    }
+}
+
+/// An action subscriptions can carry out.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct EventAction {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Key subscriptions refer to the action by.
+    #[serde(rename = "action-key")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action_key: Option<String>,
+    #[serde(rename = "display-name")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /// Inputs the action accepts, by name.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub attributes: HashMap<String, Value>,
+}
+
+/// A type of event subscriptions can filter on.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct AvailableEvent {
+    /// Fully qualified type of the event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "display-name")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Statuses events of this type are reported
+    /// with.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub statuses: Vec<String>,
+}
+
+/// Record of an event matching a subscription,
+/// and of XNAT carrying out its action.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct DeliveredEvent {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<u64>,
+    #[serde(rename = "event-type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subscription: Option<EventSubscription>,
+    /// Last status of the delivery, such as
+    /// `ACTION_COMPLETE` or `ACTION_FAILED`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(rename = "status-message")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_message: Option<String>,
+    /// When the event was delivered, in
+    /// milliseconds since the epoch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<i64>,
+    #[serde(rename = "error-state")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_state: Option<bool>,
+}
+
+/// Counts of delivered events, broken down
+/// several ways.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct DeliverySummary {
+    #[serde(rename = "event-types", default)]
+    pub event_types: HashMap<String, u64>,
+    #[serde(default)]
+    pub projects: HashMap<String, u64>,
+    #[serde(default)]
+    pub statuses: HashMap<String, u64>,
+    #[serde(default)]
+    pub subscriptions: HashMap<String, u64>,
+}
@@ -2,16 +2,29 @@ pub mod automation;
 pub mod buildinfo;
 pub mod config;
 pub mod dicomscp;
+pub mod events;
 pub mod notifications;
 pub mod plugin;
 pub mod prefs;
 pub mod siteconfig;
+pub mod token;
+pub mod user;
 
 pub use automation::Automation;
 pub use buildinfo::BuildInfo;
 pub use config::ConfigLegacy;
 pub use dicomscp::{DicomSCP, DicomSCPs};
+pub use events::{
+    AvailableEvent,
+    DeliveredEvent,
+    DeliverySummary,
+    EventAction,
+    EventFilter,
+    EventSubscription,
+};
 pub use notifications::Notifications;
 pub use plugin::{Plugin, Plugins};
 pub use prefs::Preferences;
 pub use siteconfig::SiteConfig;
+pub use token::AliasToken;
+pub use user::User;
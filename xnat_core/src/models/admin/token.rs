@@ -0,0 +1,51 @@
+use std::fmt::Debug;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A stand-in for the username and password of
+/// an account, which XNAT issues and can revoke
+/// without changing the password itself.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct AliasToken {
+    pub alias: String,
+    pub secret: String,
+    /// When XNAT expects the token to expire, in
+    /// milliseconds since the epoch.
+    #[serde(rename = "estimatedExpirationTime")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_expiration: Option<i64>,
+    /// Username of the account the token was
+    /// issued for.
+    #[serde(rename = "xdatUserId")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+}
+
+impl AliasToken {
+    /// A token known by its alias and secret.
+    pub fn new(alias: &str, secret: &str) -> Self {
+        Self {
+            alias: alias.to_owned(),
+            secret: secret.to_owned(),
+            estimated_expiration: None,
+            username: None,
+        }
+    }
+
+    /// When XNAT expects the token to expire.
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.estimated_expiration.and_then(DateTime::from_timestamp_millis)
+    }
+}
+
+impl Debug for AliasToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AliasToken")
+            .field("alias", &self.alias)
+            .field("secret", &"********")
+            .field("estimated_expiration", &self.estimated_expiration)
+            .field("username", &self.username)
+            .finish()
+    }
+}
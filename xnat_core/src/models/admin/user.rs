@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::common::FlexU64;
+
+/// An account of the XNAT site, as managed by
+/// its administrators.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct User {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(rename = "firstName")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<FlexU64>,
+    #[serde(rename = "lastName")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_name: Option<String>,
+    /// Only ever sent to XNAT; it never reports
+    /// the password of an account.
+    #[serde(skip_deserializing)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verified: Option<bool>,
+
+    // Read-only attributes reported by XNAT.
+    #[serde(rename = "lastModified", skip_serializing)]
+    last_modified: Option<u64>,
+    #[serde(rename = "lastSuccessfulLogin", skip_serializing)]
+    last_successful_login: Option<u64>,
+}
+
+impl User {
+    /// An account known by its username.
+    pub fn new(username: &str) -> Self {
+        Self {
+            username: Some(username.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    /// When the account was last modified, in
+    /// milliseconds since the epoch.
+    pub fn last_modified(&self) -> Option<u64> {
+        self.last_modified
+    }
+
+    /// When the account last logged in, in
+    /// milliseconds since the epoch.
+    pub fn last_successful_login(&self) -> Option<u64> {
+        self.last_successful_login
+    }
+}
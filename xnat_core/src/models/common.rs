@@ -203,6 +203,10 @@ pub struct ResultSet<T> {
 pub struct ResultSetData<T> {
     #[serde(rename = "Result")]
     pub result: Vec<T>,
+    /// Number of records matching the request,
+    /// regardless of how many were returned.
+    #[serde(rename = "totalRecords", default, deserialize_with = "deserialize_count")]
+    pub total_records: Option<u64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -211,10 +215,35 @@ pub struct ResultSetMeta {
     pub columns: Option<HashMap<String, String>>,
     #[serde(rename = "title")]
     pub title: Option<String>,
-    #[serde(rename = "totalRecords")]
+    #[serde(rename = "totalRecords", default, deserialize_with = "deserialize_count")]
     pub total_records: Option<u64>,
 }
 
+/// Deserialize an optional count given either as
+/// an integer or a string, as XNAT does for
+/// `totalRecords`.
+fn deserialize_count<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Count {
+        Integer(u64),
+        String(String),
+    }
+
+    match Option::<Count>::deserialize(deserializer)? {
+        Some(Count::Integer(v)) => Ok(Some(v)),
+        Some(Count::String(v)) => v
+            .trim()
+            .parse::<u64>()
+            .map(Some)
+            .map_err(serde::de::Error::custom),
+        None => Ok(None),
+    }
+}
+
 impl<T> ResultSet<T> {
     pub fn columns(&self) -> &Option<HashMap<String, String>> {
         &self.metadata.columns
@@ -226,8 +255,7 @@ impl<T> ResultSet<T> {
 
     pub fn len(&self) -> usize {
         self
-            .metadata
-            .total_records
+            .total_records()
             .and_then(|tr| (tr as usize).into())
             .or(self.data.result.len().into())
             .unwrap()
@@ -240,6 +268,55 @@ impl<T> ResultSet<T> {
     pub fn title(&self) -> &Option<String> {
         &self.metadata.title
     }
+
+    /// Number of records matching the request.
+    /// May be more than the number of results
+    /// when the listing is paged.
+    pub fn total_records(&self) -> Option<u64> {
+        self.data.total_records.or(self.metadata.total_records)
+    }
+}
+
+/// Record of some model `M` along with any
+/// columns the model does not define, such as
+/// those requested via `Query::with_columns`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct Row<M> {
+    #[serde(flatten)]
+    pub model: M,
+    /// Columns not captured by the model, keyed
+    /// as they are named by the host.
+    #[serde(flatten)]
+    pub columns: HashMap<String, serde_json::Value>,
+}
+
+impl<M> Row<M> {
+    /// Get the value of an extra column. Column
+    /// names are matched case-insensitively, as
+    /// XNAT returns them in lowercase.
+    pub fn column(&self, name: &str) -> Option<&serde_json::Value> {
+        self.columns.get(name).or_else(|| {
+            self.columns
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v)
+        })
+    }
+
+    /// Get the value of an extra column as text.
+    pub fn column_str(&self, name: &str) -> Option<&str> {
+        self.column(name).and_then(serde_json::Value::as_str)
+    }
+
+    pub fn unwrap(self) -> M {
+        self.model
+    }
+}
+
+impl<M> AsRef<M> for Row<M> {
+    fn as_ref(&self) -> &M {
+        &self.model
+    }
 }
 
 /// Custom `serde::de::Visitor` for
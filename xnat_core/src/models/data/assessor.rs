@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::models::common::FormatSpecifier;
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct Assessor {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub date: Option<String>,
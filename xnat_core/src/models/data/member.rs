@@ -0,0 +1,98 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// Group of a project which grants its members
+/// access.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(from = "String", into = "String")]
+pub enum ProjectGroup {
+    Collaborators,
+    Members,
+    Owners,
+    /// A group the project defines for itself,
+    /// by its display name.
+    Custom(String),
+}
+
+impl Display for ProjectGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Collaborators => write!(f, "Collaborators"),
+            Self::Members => write!(f, "Members"),
+            Self::Owners => write!(f, "Owners"),
+            Self::Custom(name) => write!(f, "{name}"),
+        }
+    }
+}
+
+impl From<String> for ProjectGroup {
+    fn from(value: String) -> Self {
+        match value.as_str() {
+            "Collaborators" => Self::Collaborators,
+            "Members" => Self::Members,
+            "Owners" => Self::Owners,
+            _ => Self::Custom(value),
+        }
+    }
+}
+
+impl From<&str> for ProjectGroup {
+    fn from(value: &str) -> Self {
+        value.to_owned().into()
+    }
+}
+
+impl From<ProjectGroup> for String {
+    fn from(value: ProjectGroup) -> Self {
+        value.to_string()
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ProjectMember {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(rename = "firstname")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub first_name: Option<String>,
+    #[serde(rename = "displayname")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<ProjectGroup>,
+    #[serde(rename = "GROUP_ID")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_id: Option<String>,
+    #[serde(rename = "lastname")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_name: Option<String>,
+    #[serde(rename = "login")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+
+    // Additional data that can be utilized at
+    // runtime.
+    #[serde(skip_serializing)]
+    pub project: Option<String>,
+}
+
+impl ProjectMember {
+    /// A member of `group`, known by their
+    /// username.
+    pub fn new<G: Into<ProjectGroup>>(username: &str, group: G) -> Self {
+        Self {
+            group: Some(group.into()),
+            username: Some(username.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    /// Name of the member as XNAT displays it,
+    /// falling back on their username.
+    pub fn display_name(&self) -> Option<String> {
+        match (&self.first_name, &self.last_name) {
+            (Some(first), Some(last)) => Some(format!("{first} {last}")),
+            (Some(name), None) | (None, Some(name)) => Some(name.to_owned()),
+            (None, None) => self.username.to_owned(),
+        }
+    }
+}
@@ -1,15 +1,19 @@
 pub mod assessor;
 pub mod experiment;
+pub mod member;
 pub mod project;
 pub mod pipeline;
+pub mod prearchive;
 pub mod resource;
 pub mod scan;
 pub mod subject;
 
 pub use assessor::Assessor;
 pub use experiment::Experiment;
+pub use member::{ProjectGroup, ProjectMember};
 pub use pipeline::PipelineConfig;
-pub use project::Project;
+pub use prearchive::{PrearchiveSession, PrearchiveStatus};
+pub use project::{Project, SharedProject};
 pub use resource::Resource;
 pub use scan::Scan;
 pub use subject::Subject;
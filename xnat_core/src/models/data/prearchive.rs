@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+
+/// State of a session held in the prearchive.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PrearchiveStatus {
+    Archiving,
+    Building,
+    Conflict,
+    Deleting,
+    Error,
+    Moving,
+    QueuedArchiving,
+    QueuedBuilding,
+    QueuedDeleting,
+    QueuedMoving,
+    Ready,
+    Receiving,
+    /// A status this crate does not know of.
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct PrearchiveSession {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub autoarchive: Option<String>,
+    #[serde(rename = "folderName")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub folder_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scan_date: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scan_time: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<PrearchiveStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uploaded: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(rename = "VISIT")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visit: Option<String>,
+
+    // Read-only fields, only meant for the
+    // host to modify.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lastmod: Option<String>,
+
+    // Additional data that can be utilized at
+    // runtime.
+    /// Number of scans, known once the session
+    /// is inspected.
+    #[serde(skip_serializing)]
+    pub scan_count: Option<u64>,
+}
+
+impl PrearchiveSession {
+    /// Get READ-ONLY last-modified datetime.
+    pub fn last_modified(&self) -> &Option<String> {
+        &self.lastmod
+    }
+
+    /// Path of the session as the prearchive
+    /// services refer to it, such as
+    /// `/prearchive/projects/{project}/{timestamp}/{folder}`.
+    pub fn src(&self) -> crate::Result<String> {
+        let (project, timestamp, folder) = self.identifiers()?;
+        Ok(format!("/prearchive/projects/{project}/{timestamp}/{folder}"))
+    }
+
+    /// The project, timestamp and folder name
+    /// which identify the session.
+    pub(crate) fn identifiers(&self) -> crate::Result<(&str, &str, &str)> {
+        fn required<'a>(value: Option<&'a str>, name: &str) -> crate::Result<&'a str> {
+            value.ok_or_else(|| crate::Error::IdentifierRequired(name.to_owned()))
+        }
+        Ok((
+            required(self.project.as_deref(), "project id")?,
+            required(self.timestamp.as_deref(), "timestamp")?,
+            required(self.folder_name.as_deref().or(self.name.as_deref()), "folder name")?,
+        ))
+    }
+}
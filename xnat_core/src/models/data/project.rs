@@ -32,3 +32,24 @@ pub struct Project {
     #[serde(skip_serializing)]
     pub subjects: Option<Vec<Subject>>,
 }
+
+/// A project a subject or experiment is shared
+/// into, or owned by.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct SharedProject {
+    #[serde(rename = "ID")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Label the item is known by within the
+    /// project.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(rename = "secondary_ID")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secondary_id: Option<String>,
+    #[serde(rename = "URI")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uri: Option<String>,
+}
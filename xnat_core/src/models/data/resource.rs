@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct Resource {
     #[serde(rename = "cat_desc")]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -14,6 +14,10 @@ pub struct Resource {
     pub collection: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub content: Option<String>,
+    /// Checksum of the file, as reported by the
+    /// host. Usually an MD5 digest.
+    #[serde(skip_serializing)]
+    pub digest: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub element_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -35,6 +39,11 @@ pub struct Resource {
     pub size: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tags: Option<String>,
+    /// Path to the file on the host, as reported
+    /// in file listings.
+    #[serde(rename = "URI")]
+    #[serde(skip_serializing)]
+    pub uri: Option<String>,
 
     #[serde(rename = "sortBy")]
     #[serde(skip_serializing_if = "Option::is_none")]
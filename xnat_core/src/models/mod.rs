@@ -3,30 +3,44 @@ pub mod common;
 pub mod data;
 
 pub use admin::{
+    AliasToken,
     Automation,
+    AvailableEvent,
     BuildInfo,
     ConfigLegacy,
+    DeliveredEvent,
+    DeliverySummary,
     DicomSCP,
     DicomSCPs,
+    EventAction,
+    EventFilter,
+    EventSubscription,
     Notifications,
     Plugin,
     Plugins,
     Preferences,
-    SiteConfig
+    SiteConfig,
+    User,
 };
 pub use common::{
     FlexU64,
     FormatSpecifier,
     Items,
     Item,
-    ResultSet
+    ResultSet,
+    Row,
 };
 pub use data::{
     Assessor,
     Experiment,
     PipelineConfig,
+    PrearchiveSession,
+    PrearchiveStatus,
     Project,
+    ProjectGroup,
+    ProjectMember,
     Resource,
     Scan,
+    SharedProject,
     Subject,
 };
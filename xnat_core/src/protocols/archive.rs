@@ -0,0 +1,250 @@
+use std::fmt::Debug;
+use std::fs::{self, File};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use zip::ZipArchive;
+
+use crate::models::Resource;
+use crate::uri::data::{ExperimentUri, ProjectUriLegacy, SubjectUriLegacy};
+use crate::Error;
+use super::download::resource_uri;
+
+/// Selection of files XNAT bundles into a zip
+/// archive.
+#[derive(Clone, Debug)]
+pub enum ZipSource {
+    /// Every scan of an experiment.
+    Experiment(String),
+    /// Selected scans of an experiment. Only the
+    /// named resources of each scan are included,
+    /// or all of them if none are named.
+    Scans {
+        experiment: String,
+        scans:      Vec<String>,
+        resources:  Vec<String>,
+    },
+    /// Selected resources of the project,
+    /// subject, experiment or scan identified by
+    /// `parent`.
+    Resources {
+        parent:      Box<Resource>,
+        collections: Vec<String>,
+    },
+}
+
+impl ZipSource {
+    /// Select every scan of an experiment.
+    pub fn experiment(experiment: &str) -> Self {
+        Self::Experiment(experiment.to_owned())
+    }
+
+    /// Select scans of an experiment, with all of
+    /// their resources.
+    pub fn scans<S: AsRef<str>>(experiment: &str, scans: &[S]) -> Self {
+        Self::Scans {
+            experiment: experiment.to_owned(),
+            scans: to_owned_all(scans),
+            resources: Vec::new(),
+        }
+    }
+
+    /// Select resources of whatever `parent`
+    /// identifies. The collection of `parent`
+    /// itself is ignored.
+    pub fn resources<S: AsRef<str>>(parent: &Resource, collections: &[S]) -> Self {
+        Self::Resources {
+            parent: Box::new(parent.to_owned()),
+            collections: to_owned_all(collections),
+        }
+    }
+
+    /// Only include the named resources of the
+    /// selected scans.
+    pub fn with_resources<S: AsRef<str>>(self, resources: &[S]) -> Self {
+        match self {
+            Self::Experiment(experiment) => Self::Scans {
+                experiment,
+                scans: vec!["ALL".into()],
+                resources: to_owned_all(resources),
+            },
+            Self::Scans { experiment, scans, .. } => Self::Scans {
+                experiment,
+                scans,
+                resources: to_owned_all(resources),
+            },
+            Self::Resources { parent, .. } => Self::Resources {
+                parent,
+                collections: to_owned_all(resources),
+            },
+        }
+    }
+
+    /// Build the URI which lists the selected
+    /// files.
+    pub(crate) fn files_uri<V>(&self, version: &V) -> crate::Result<String>
+    where
+        V: ExperimentUri + ProjectUriLegacy + SubjectUriLegacy,
+    {
+        let uri = match self {
+            Self::Experiment(experiment) => {
+                version
+                    .experiment_data()
+                    .with_experiment(experiment)
+                    .scans()
+                    .with_scan("ALL")
+                    .files()
+            },
+            Self::Scans { experiment, scans, resources } if resources.is_empty() => {
+                version
+                    .experiment_data()
+                    .with_experiment(experiment)
+                    .scans()
+                    .with_scans(scans)
+                    .files()
+            },
+            Self::Scans { experiment, scans, resources } => {
+                let experiment = version.experiment_data().with_experiment(experiment);
+                let scans = experiment.scans().with_scans(scans);
+                scans.resources().with_resource(resources.join(",")).files()
+            },
+            Self::Resources { parent, collections } => {
+                let mut parent = parent.as_ref().to_owned();
+                parent.collection = Some(collections.join(","));
+                return resource_uri(version, &parent)
+            },
+        }?;
+        Ok(uri)
+    }
+}
+
+/// Function which chooses where a file of an
+/// archive is written, relative to the
+/// destination, or skips it.
+type PlaceFn = Arc<dyn Fn(&Path) -> Option<PathBuf> + Send + Sync>;
+
+/// Directory layout files of a zip archive are
+/// extracted into.
+#[derive(Clone, Default)]
+pub enum ZipLayout {
+    /// Every file directly within the
+    /// destination. Files sharing a name are
+    /// given a numeric suffix.
+    Flat,
+    /// Paths as XNAT gives them, such as
+    /// `{experiment}/scans/{scan}/resources/{resource}/files/{file}`.
+    #[default]
+    Xnat,
+    /// Paths chosen by a function of the path
+    /// XNAT gives. Files it returns `None` for
+    /// are skipped.
+    Custom(PlaceFn),
+}
+
+impl ZipLayout {
+    /// Lay out files by a function of the path
+    /// XNAT gives them.
+    pub fn custom<F>(place: F) -> Self
+    where
+        F: Fn(&Path) -> Option<PathBuf> + Send + Sync + 'static,
+    {
+        Self::Custom(Arc::new(place))
+    }
+
+    /// Where a file of the archive is written,
+    /// relative to the destination.
+    fn place(&self, path: &Path) -> Option<PathBuf> {
+        match self {
+            Self::Flat => path.file_name().map(PathBuf::from),
+            Self::Xnat => Some(path.to_owned()),
+            Self::Custom(place) => place(path),
+        }
+    }
+}
+
+impl Debug for ZipLayout {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Flat => write!(f, "Flat"),
+            Self::Xnat => write!(f, "Xnat"),
+            Self::Custom(_) => write!(f, "Custom(..)"),
+        }
+    }
+}
+
+/// Extract the files of a zip archive into
+/// `dir`, laid out by `layout`. Returns the paths
+/// written.
+///
+/// Entries which would be written outside of
+/// `dir` fail the extraction with
+/// `Error::UnsafePath`, before anything is
+/// written.
+pub fn extract_zip(archive: &Path, dir: &Path, layout: &ZipLayout) -> crate::Result<Vec<PathBuf>> {
+    let mut archive = ZipArchive::new(File::open(archive)?)?;
+
+    let mut placed = Vec::new();
+    for index in 0..archive.len() {
+        let entry = archive.by_index_raw(index)?;
+        if entry.is_dir() {
+            continue
+        }
+        let name = PathBuf::from(entry.name());
+        let source = enclosed(&name).ok_or_else(|| Error::UnsafePath(name.to_owned()))?;
+        if let Some(target) = layout.place(&source) {
+            let target = enclosed(&target).ok_or(Error::UnsafePath(target))?;
+            placed.push((index, target));
+        }
+    }
+    if matches!(layout, ZipLayout::Flat) {
+        deduplicate(&mut placed);
+    }
+
+    let mut written = Vec::with_capacity(placed.len());
+    for (index, target) in placed {
+        let path = dir.join(target);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut entry = archive.by_index(index)?;
+        std::io::copy(&mut entry, &mut File::create(&path)?)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// The path, if it only descends into the
+/// directory it is relative to.
+pub(crate) fn enclosed(path: &Path) -> Option<PathBuf> {
+    let enclosed = path
+        .components()
+        .filter(|c| *c != Component::CurDir)
+        .collect::<PathBuf>();
+    let descends = path
+        .components()
+        .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+    (descends && enclosed.components().next().is_some()).then_some(enclosed)
+}
+
+/// Give files placed at the same path a numeric
+/// suffix, as in `image (1).dcm`.
+fn deduplicate(placed: &mut [(usize, PathBuf)]) {
+    let mut seen = std::collections::HashSet::new();
+    for (_, path) in placed.iter_mut() {
+        let original = path.to_owned();
+        let mut count = 0;
+        while !seen.insert(path.to_owned()) {
+            count += 1;
+            let stem = original.file_stem().unwrap_or_default().to_string_lossy();
+            let name = match original.extension() {
+                Some(ext) => format!("{stem} ({count}).{}", ext.to_string_lossy()),
+                None => format!("{stem} ({count})"),
+            };
+            *path = original.with_file_name(name);
+        }
+    }
+}
+
+fn to_owned_all<S: AsRef<str>>(values: &[S]) -> Vec<String> {
+    values.iter().map(|v| v.as_ref().to_owned()).collect()
+}
@@ -1,7 +1,8 @@
 use async_trait::async_trait;
 
 use crate::client::{Xnat, ClientCore, ClientREST};
-use crate::models::{Experiment, Project, Resource, Scan, Subject};
+use crate::models::{Experiment, Project, Resource, Scan, Subject, User};
+use crate::uri::admin::UsersUri;
 use crate::uri::data::resources::ResourcesUriBuilder;
 use crate::uri::data::{
     ExperimentUri,
@@ -9,45 +10,45 @@ use crate::uri::data::{
     SubjectUriLegacy,
 };
 use crate::version::Version;
-use crate::UriBuilder;
-use super::crud::{CrudError, Create};
+use crate::{Error, UriBuilder};
+use super::crud::{try_retrieve, Create};
 
 /// Takes the `Option` value for the specified
 /// attribute, returning a `Result`. Otherwise
 /// fails and returns a
-/// `CrudError::IdentifierRequired`.
+/// `Error::IdentifierRequired`.
 macro_rules! acquire_identifier {
     ($attr:expr, $error:literal) => {
-        $attr.take().ok_or(CrudError::IdentifierRequired($error.to_string()))
+        $attr.take().ok_or(Error::IdentifierRequired($error.to_string()))
     };
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 impl<V> Create<Project> for Xnat<V>
 where
     Self: ClientCore<Version = V> + ClientREST,
     V: Version + ProjectUriLegacy,
 {
-    async fn create_once(&self, model: Project) -> anyhow::Result<Project> {
+    async fn create_once(&self, model: Project) -> crate::Result<Project> {
         let mut model_clone = model.clone();
         let project = acquire_identifier!(model_clone.id, "project id")?;
 
-        self.put(&self.version().project_data().with_id(project))
+        let request = self
+            .put(&self.version().project_data().with_id(project))
             .await?
-            .json(&model_clone)
-            .send()
-            .await?;
+            .json(&model_clone);
+        try_retrieve(self.execute(request).await?, |_| async {}).await?;
         Ok(model)
     }
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 impl<V> Create<Subject> for Xnat<V>
 where
     Self: ClientCore<Version = V> + ClientREST,
     V: Version + ProjectUriLegacy + SubjectUriLegacy,
 {
-    async fn create_once(&self, model: Subject) -> anyhow::Result<Subject> {
+    async fn create_once(&self, model: Subject) -> crate::Result<Subject> {
         let mut model_clone = model.clone();
         let project = acquire_identifier!(model_clone.project, "project id")?;
         let subject = acquire_identifier!(model_clone.id, "subject id")?;
@@ -58,23 +59,22 @@ where
             .with_id(project)
             .subjects()
             .with_subject(subject);
-        self
+        let request = self
             .put(&uri)
             .await?
-            .json(&model_clone)
-            .send()
-            .await?;
+            .json(&model_clone);
+        try_retrieve(self.execute(request).await?, |_| async {}).await?;
         Ok(model)
     }
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 impl<V> Create<Experiment> for Xnat<V>
 where
     Self: ClientCore<Version = V> + ClientREST,
     V: Version + ExperimentUri + ProjectUriLegacy + SubjectUriLegacy,
 {
-    async fn create_once(&self, model: Experiment) -> anyhow::Result<Experiment> {
+    async fn create_once(&self, model: Experiment) -> crate::Result<Experiment> {
         let mut model_clone = model.clone();
         let project = acquire_identifier!(model_clone.project, "project id")?;
         let subject = acquire_identifier!(
@@ -98,22 +98,22 @@ where
             .with_subject(subject)
             .experiments()
             .with_experiment(session);
-        self.put(&uri)
+        let request = self
+            .put(&uri)
             .await?
-            .json(&model_clone)
-            .send()
-            .await?;
+            .json(&model_clone);
+        try_retrieve(self.execute(request).await?, |_| async {}).await?;
         Ok(model)
     }
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 impl<V> Create<Scan> for Xnat<V>
 where
     Self: ClientCore<Version = V> + ClientREST,
     V: Version + ExperimentUri + ProjectUriLegacy + SubjectUriLegacy,
 {
-    async fn create_once(&self, model: Scan) -> anyhow::Result<Scan> {
+    async fn create_once(&self, model: Scan) -> crate::Result<Scan> {
         let mut model_clone = model.clone();
         let project = acquire_identifier!(model_clone.project, "project id")?;
         let subject = acquire_identifier!(model_clone.subject, "subject id")?;
@@ -128,22 +128,22 @@ where
             .with_subject(subject)
             .experiments()
             .with_experiment(session);
-        self.put(&uri.scans().with_scan(scan))
+        let request = self
+            .put(&uri.scans().with_scan(scan))
             .await?
-            .json(&model_clone)
-            .send()
-            .await?;
+            .json(&model_clone);
+        try_retrieve(self.execute(request).await?, |_| async {}).await?;
         Ok(model)
     }
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 impl<V> Create<Resource> for Xnat<V>
 where
     Self: ClientCore<Version = V> + ClientREST,
     V: Version + ExperimentUri + ProjectUriLegacy + SubjectUriLegacy,
 {
-    async fn create_once(&self, model: Resource) -> anyhow::Result<Resource> {
+    async fn create_once(&self, model: Resource) -> crate::Result<Resource> {
         let mut model_clone = model.clone();
         model_clone.project.take();
         model_clone.subject.take();
@@ -203,7 +203,7 @@ where
                 project: Some(pjt),
                 ..
             } => self.version().project_data().with_id(pjt).build(),
-            _ => return Err(CrudError::IdentifierRequired("any identifiers".into()).into())
+            _ => return Err(Error::IdentifierRequired("any identifiers".into()))
         }?;
         let uri = ResourcesUriBuilder::default().with_parent(&uri);
         let uri = match &model {
@@ -215,11 +215,31 @@ where
             },
             _ => uri
         };
-        self.put(&uri)
+        let request = self
+            .put(&uri)
+            .await?
+            .json(&model_clone);
+        try_retrieve(self.execute(request).await?, |_| async {}).await?;
+        Ok(model)
+    }
+}
+
+#[async_trait]
+impl<V> Create<User> for Xnat<V>
+where
+    Self: ClientCore<Version = V> + ClientREST,
+    V: Version + UsersUri,
+{
+    async fn create_once(&self, model: User) -> crate::Result<User> {
+        if model.username.is_none() {
+            return Err(Error::IdentifierRequired("username".into()));
+        }
+
+        let request = self
+            .post(&self.version().users())
             .await?
-            .json(&model_clone)
-            .send()
-            .await?;
+            .json(&model);
+        try_retrieve(self.execute(request).await?, |_| async {}).await?;
         Ok(model)
     }
 }
@@ -3,30 +3,15 @@ use std::{future::Future, iter::IntoIterator, pin::Pin, vec::IntoIter};
 use async_trait::async_trait;
 use reqwest::{Response, StatusCode};
 use serde::{de::DeserializeOwned, Serialize};
-use thiserror::Error;
+use serde_json::Value;
 
-use crate::{models::{Items, ResultSet}, ClientREST, UriBuilder};
+use crate::{models::{Items, ResultSet}, ClientREST, Error, ErrorResponse, UriBuilder};
+use super::paging::{Page, Paging, ResultStream, Rows};
+use super::query::Query;
 
 /// Complex type representing a pinned future with
 /// a specified output.
-type PinnedFuture<'f, O> = Pin<Box<dyn Future<Output = anyhow::Result<O>> + 'f>>;
-
-/// Errors specific to the purpose of interactions
-/// between an XNAT client and the host during
-/// protocol transactions.
-#[derive(Debug, Error)]
-pub enum CrudError {
-    #[error("CRUD operation requires `{0}`")]
-    IdentifierRequired(String),
-    #[error("host XNAT experienced an internal error ({0})")]
-    HostError(StatusCode),
-    #[error("resource is not available ({0})")]
-    NotAvailable(StatusCode),
-    #[error("could not create resource ({0})")]
-    NotCreated(StatusCode),
-    #[error("could not retrieve resource ({0})")]
-    NotFound(StatusCode),
-}
+pub(crate) type PinnedFuture<'f, O> = Pin<Box<dyn Future<Output = crate::Result<O>> + Send + 'f>>;
 
 /// Type is able to implement CREATE requests for
 /// a particular model. Upon creation, these
@@ -34,10 +19,10 @@ pub enum CrudError {
 /// individual results, and then a
 /// `Ok(Self::Model)` response if the request is
 /// successful.
-#[async_trait(?Send)]
+#[async_trait]
 pub trait Create<M>
 where
-    M: Clone + Serialize,
+    M: Clone + Send + Sync + Serialize,
 {
     /// Attempt to send a CREATE request to the
     /// XNAT server for **multiple** models.
@@ -53,20 +38,20 @@ where
     }
     /// Attempt to send a CREATE request to the
     /// XNAT server for **one** model.
-    async fn create_once(&self, model: M) -> anyhow::Result<M>;
+    async fn create_once(&self, model: M) -> crate::Result<M>;
 }
 
 /// Type is able to implement RETRIEVE requests
 /// for a particular model.
-#[async_trait(?Send)]
+#[async_trait]
 pub trait Retrieve<M>
 where
-    M: Clone + DeserializeOwned,
+    M: Clone + Send + Sync + DeserializeOwned,
 {
     /// Get all instances of a particular model
     /// available to the user via the XNAT host.
     #[inline(never)]
-    async fn get_all(&self) -> anyhow::Result<Vec<M>>
+    async fn get_all(&self) -> crate::Result<Vec<M>>
     where
         M: Default,
     {
@@ -75,7 +60,7 @@ where
     /// Get all instances of a particular model
     /// using another model as the query
     /// parameters for the request.
-    async fn get_any_from(&self, model: &M) -> anyhow::Result<Vec<M>>;
+    async fn get_any_from(&self, model: &M) -> crate::Result<Vec<M>>;
     /// Get all instances of a particular model
     /// using another model as the query
     /// parameters for the request.
@@ -90,17 +75,13 @@ where
     /// can expect the result to return an `Items`
     /// model.
     #[inline(never)]
-    async fn get_any_items_from<UB>(&self, uri: &UB, model: &M) -> anyhow::Result<Items<M>>
+    async fn get_any_items_from<UB>(&self, uri: &UB, model: &M) -> crate::Result<Items<M>>
     where
         Self: ClientREST,
         M: Serialize,
-        UB: UriBuilder,
+        UB: UriBuilder + Sync,
     {
-        let res = try_retrieve(
-            self.get(uri).await?.query(model).send().await?,
-            |r| async { r }
-        ).await?;
-        Ok(res.json::<Items<M>>().await?)
+        self.get_items_from(uri, model).await
     }
     /// Get all instances of a particular model
     /// using another model as the query
@@ -116,37 +97,199 @@ where
     /// `ResultSet` model.
     #[allow(unused_variables)]
     #[inline(never)]
-    async fn get_any_result_from<UB>(&self, uri: &UB, model: &M) -> anyhow::Result<ResultSet<M>>
+    async fn get_any_result_from<UB>(&self, uri: &UB, model: &M) -> crate::Result<ResultSet<M>>
+    where
+        Self: ClientREST,
+        M: Serialize,
+        UB: UriBuilder + Sync,
+    {
+        self.get_query_result_from(uri, model, &Paging::default()).await
+    }
+    /// Get the records of a listing as an
+    /// `Items` object containing a series of
+    /// some type `T`, using a model as the query
+    /// parameters for the request.
+    #[inline(never)]
+    async fn get_items_from<UB, T>(&self, uri: &UB, model: &M) -> crate::Result<Items<T>>
+    where
+        Self: ClientREST,
+        M: Serialize,
+        T: DeserializeOwned,
+        UB: UriBuilder + Sync,
+    {
+        let res = try_retrieve(
+            self.execute(self.get(uri).await?.query(model)).await?,
+            |r| async { r }
+        ).await?;
+        Ok(res.json::<Items<T>>().await?)
+    }
+    /// Get one page of the instances of a
+    /// particular model using another model as
+    /// the query parameters for the request.
+    #[inline(never)]
+    async fn get_page_from(&self, model: &M, paging: &Paging) -> crate::Result<Page<M>>
+    where
+        M: Serialize,
+    {
+        self.query(&Query::new(model.clone()).with_paging(paging)).await
+    }
+    /// Get the records of a listing as a
+    /// `ResultSet` of some type `T`, sending
+    /// listing parameters such as `offset`,
+    /// `limit` or `columns` along with the model.
+    #[inline(never)]
+    async fn get_query_result_from<UB, Q, T>(&self, uri: &UB, model: &M, params: &Q) -> crate::Result<ResultSet<T>>
     where
         Self: ClientREST,
         M: Serialize,
-        UB: UriBuilder,
+        Q: Serialize + Sync,
+        T: DeserializeOwned,
+        UB: UriBuilder + Sync,
     {
         let res = try_retrieve(
-            self.get(uri).await?.query(model).send().await?,
+            self.execute(self.get(uri).await?.query(model).query(params)).await?,
             |r| async { r }
         ).await?;
-        Ok(res.json::<ResultSet<M>>().await?)
+        Ok(res.json::<ResultSet<T>>().await?)
     }
     /// Get one instance of a particular model
     /// using another model as the query
     /// parameters for the request.
     #[inline(never)]
-    async fn get_one_from(&self, model: &M) -> anyhow::Result<M>
+    async fn get_one_from(&self, model: &M) -> crate::Result<M>
     {
         match self.get_any_from(model).await?.first() {
-            None => Err(CrudError::NotFound(StatusCode::from_u16(400)?).into()),
+            None => Err(Error::from_status(ErrorResponse::from_status(StatusCode::NOT_FOUND))),
             Some(m) => Ok(m.to_owned())
         }
     }
+    /// Stream all instances of a particular model
+    /// using another model as the query
+    /// parameters, requesting `page_size`
+    /// instances at a time.
+    ///
+    /// ```no_compile
+    /// let mut experiments = client.stream_from(&Experiment::default(), 500);
+    /// while let Some(experiment) = experiments.next().await {
+    ///     let experiment = experiment?;
+    ///     println!("{}/{:?}", experiments.position(), experiments.total_records());
+    /// }
+    /// ```
+    fn stream_from<'a>(&'a self, model: &'a M, page_size: u64) -> ResultStream<'a, M>
+    where
+        Self: Sync,
+        M: Serialize,
+    {
+        self.stream_query(Query::new(model.clone()), page_size)
+    }
+    /// Stream all instances of a particular model
+    /// matching a query, requesting `page_size`
+    /// instances at a time. Any paging set on the
+    /// query is replaced.
+    ///
+    /// Listings the host cannot page are
+    /// retrieved once, and held in full by the
+    /// stream rather than requested again for
+    /// each page.
+    fn stream_query<'a>(&'a self, query: Query<M>, page_size: u64) -> ResultStream<'a, M>
+    where
+        Self: Sync,
+        M: Serialize + 'a,
+    {
+        ResultStream::new(page_size, move |paging| {
+            let query = query.clone().with_paging(&paging);
+            Box::pin(async move {
+                let page = match self.fetch_rows(&query).await? {
+                    Rows::Page(page) => page,
+                    Rows::All(rows) => {
+                        let rest = Paging { offset: paging.offset, limit: None };
+                        query.with_paging(&rest).apply(rows)
+                    },
+                };
+                Ok(page.try_map(serde_json::from_value)?)
+            })
+        })
+    }
+    /// Get the instances of a particular model
+    /// matching a query.
+    #[inline(never)]
+    async fn query(&self, query: &Query<M>) -> crate::Result<Page<M>>
+    where
+        M: Serialize,
+    {
+        self.select(query).await
+    }
+    /// Get the records matching a query as raw
+    /// JSON, including any extra columns it
+    /// requested.
+    ///
+    /// Listings the host cannot page are
+    /// retrieved in full, then filtered, sorted
+    /// and paged on the client.
+    #[inline(never)]
+    async fn query_rows(&self, query: &Query<M>) -> crate::Result<Page<Value>>
+    where
+        M: Serialize,
+    {
+        Ok(match self.fetch_rows(query).await? {
+            Rows::Page(page) => page,
+            Rows::All(rows) => query.apply(rows),
+        })
+    }
+    /// Request the records matching a query as
+    /// raw JSON, either the page it selects or,
+    /// where the host cannot page the listing,
+    /// every record.
+    ///
+    /// Types which cannot query the host directly
+    /// retrieve all instances matching the model.
+    #[inline(never)]
+    async fn fetch_rows(&self, query: &Query<M>) -> crate::Result<Rows<Value>>
+    where
+        M: Serialize,
+    {
+        let rows = self
+            .get_any_from(query.model())
+            .await?
+            .iter()
+            .map(serde_json::to_value)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Rows::All(rows))
+    }
+    /// Get the records matching a query as some
+    /// type `T`, deserialized from the columns the
+    /// host returned.
+    ///
+    /// ```no_compile
+    /// #[derive(Deserialize)]
+    /// struct Session {
+    ///     #[serde(rename = "ID")]
+    ///     id: String,
+    ///     label: String,
+    ///     #[serde(rename = "xnat:mrsessiondata/fieldstrength")]
+    ///     field_strength: Option<String>,
+    /// }
+    ///
+    /// let query = Query::new(Experiment::default())
+    ///     .with_columns(&["ID", "label", "xnat:mrSessionData/fieldStrength"]);
+    /// let sessions = client.select::<Session>(&query).await?;
+    /// ```
+    #[inline(never)]
+    async fn select<T>(&self, query: &Query<M>) -> crate::Result<Page<T>>
+    where
+        M: Serialize,
+        T: DeserializeOwned + Send,
+    {
+        Ok(self.query_rows(query).await?.try_map(serde_json::from_value)?)
+    }
 }
 
 /// Type is able to implement UPDATE or UPSERT
 /// requests for a particular model.
-#[async_trait(?Send)]
+#[async_trait]
 pub trait Update<M>
 where
-    M: Clone + Serialize,
+    M: Clone + Send + Sync + Serialize,
 {
     /// Attempt to send **multiple** UPDATE
     /// requests to the XNAT host.
@@ -162,15 +305,15 @@ where
     }
     /// Attempt to send **one** UPDATE request to
     /// the XNAT host.
-    async fn update_once(&self, model: M) -> anyhow::Result<M>;
+    async fn update_once(&self, model: M) -> crate::Result<M>;
 }
 
 /// Type is able to implement DELETE requests for
 /// a particular model.
-#[async_trait(?Send)]
+#[async_trait]
 pub trait Delete<M>
 where
-    M: Clone + Serialize
+    M: Clone + Send + Sync + Serialize
 {
     /// Attempt to send **multiple** DELETE
     /// requests to the XNAT host.
@@ -186,25 +329,19 @@ where
     }
     /// Attempt to send **one** DELETE request to
     /// the XNAT host.
-    async fn delete_once(&self, model: M) -> anyhow::Result<M>;
+    async fn delete_once(&self, model: M) -> crate::Result<M>;
 }
 
 /// Utility function to shortcut the handling of
 /// the returning value of a sent request.
-pub async fn try_retrieve<T, Callback, F>(response: Response, call: Callback) -> anyhow::Result<T>
+pub async fn try_retrieve<T, Callback, F>(response: Response, call: Callback) -> crate::Result<T>
 where
     F: Future<Output = T>,
     Callback: FnOnce(Response) -> F,
 {
-    let status = response.status();
-
-    if status.is_success() {
+    if response.status().is_success() {
         Ok(call(response).await)
-    } else if status.is_client_error() && status == 400 {
-        Err(CrudError::NotFound(status).into())
-    } else if status.is_client_error() {
-        Err(CrudError::NotAvailable(status).into())
     } else {
-        Err(CrudError::HostError(status).into())
+        Err(Error::from_response(response).await)
     }
 }
@@ -1,48 +1,46 @@
 use async_trait::async_trait;
 
 use crate::uri::data::resources::ResourcesUriBuilder;
-use crate::UriBuilder;
+use crate::{Error, UriBuilder};
 use crate::client::{Xnat, ClientCore, ClientREST};
 use crate::models::{Experiment, Project, Resource, Scan, Subject};
 use crate::uri::data::{ExperimentUri, ProjectUriLegacy, SubjectUriLegacy};
 use crate::version::Version;
-use super::crud::{CrudError, Delete};
+use super::crud::{try_retrieve, Delete};
 
 /// Takes the `Option` value for the specified
 /// attribute, returning a `Result`. Otherwise
 /// fails and returns a
-/// `CrudError::IdentifierRequired`.
+/// `Error::IdentifierRequired`.
 macro_rules! acquire_identifier {
     ($attr:expr, $error:literal) => {
-        $attr.take().ok_or(CrudError::IdentifierRequired($error.to_string()))
+        $attr.take().ok_or(Error::IdentifierRequired($error.to_string()))
     };
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 impl<V> Delete<Project> for Xnat<V>
 where
     Self: ClientCore<Version = V> + ClientREST,
     V: Version + ProjectUriLegacy,
 {
-    async fn delete_once(&self, model: Project) -> anyhow::Result<Project> {
+    async fn delete_once(&self, model: Project) -> crate::Result<Project> {
         let mut model_clone = model.clone();
         let project = acquire_identifier!(model_clone.id, "project id")?;
 
-        self.delete(&self.version().project_data().with_id(project))
-            .await?
-            .send()
-            .await?;
+        let uri = self.version().project_data().with_id(project);
+        try_retrieve(self.execute(self.delete(&uri).await?).await?, |_| async {}).await?;
         Ok(model)
     }
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 impl<V> Delete<Subject> for Xnat<V>
 where
     Self: ClientCore<Version = V> + ClientREST,
     V: Version + ProjectUriLegacy + SubjectUriLegacy,
 {
-    async fn delete_once(&self, model: Subject) -> anyhow::Result<Subject> {
+    async fn delete_once(&self, model: Subject) -> crate::Result<Subject> {
         let mut model_clone = model.clone();
         let project = acquire_identifier!(model_clone.project, "project id")?;
         let subject = acquire_identifier!(
@@ -57,18 +55,18 @@ where
             .with_id(project)
             .subjects()
             .with_subject(subject);
-        self.delete(&uri).await?.send().await?;
+        try_retrieve(self.execute(self.delete(&uri).await?).await?, |_| async {}).await?;
         Ok(model)
     }
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 impl<V> Delete<Experiment> for Xnat<V>
 where
     Self: ClientCore<Version = V> + ClientREST,
     V: Version + ExperimentUri + ProjectUriLegacy + SubjectUriLegacy,
 {
-    async fn delete_once(&self, model: Experiment) -> anyhow::Result<Experiment> {
+    async fn delete_once(&self, model: Experiment) -> crate::Result<Experiment> {
         let model_clone = model.clone();
         let project = acquire_identifier!(
             model_clone
@@ -96,18 +94,18 @@ where
             .with_subject(subject)
             .experiments()
             .with_experiment(session);
-        self.delete(&uri).await?.send().await?;
+        try_retrieve(self.execute(self.delete(&uri).await?).await?, |_| async {}).await?;
         Ok(model)
     }
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 impl<V> Delete<Scan> for Xnat<V>
 where
     Self: ClientCore<Version = V> + ClientREST,
     V: Version + ExperimentUri + ProjectUriLegacy + SubjectUriLegacy,
 {
-    async fn delete_once(&self, model: Scan) -> anyhow::Result<Scan> {
+    async fn delete_once(&self, model: Scan) -> crate::Result<Scan> {
         let mut model_clone = model.clone();
 
         let project = acquire_identifier!(
@@ -128,18 +126,18 @@ where
             .with_subject(subject)
             .experiments()
             .with_experiment(session);
-        self.delete(&uri.scans().with_scan(scan)).await?.send().await?;
+        try_retrieve(self.execute(self.delete(&uri.scans().with_scan(scan)).await?).await?, |_| async {}).await?;
         Ok(model)
     }
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 impl<V> Delete<Resource> for Xnat<V>
 where
     Self: ClientCore<Version = V> + ClientREST,
     V: Version + ExperimentUri + ProjectUriLegacy + SubjectUriLegacy,
 {
-    async fn delete_once(&self, model: Resource) -> anyhow::Result<Resource> {
+    async fn delete_once(&self, model: Resource) -> crate::Result<Resource> {
         let uri = match &model {
             Resource {
                 project: Some(pjt),
@@ -199,7 +197,7 @@ where
                     .with_id(pjt)
                     .build()
             },
-            _ => return Err(CrudError::IdentifierRequired("any identifiers".into()).into())
+            _ => return Err(Error::IdentifierRequired("any identifiers".into()))
         }?;
         let uri = ResourcesUriBuilder::default().with_parent(&uri);
         let uri = match &model {
@@ -212,7 +210,7 @@ where
             _ => uri,
         };
 
-        self.delete(&uri).await?.send().await?;
+        try_retrieve(self.execute(self.delete(&uri).await?).await?, |_| async {}).await?;
         Ok(model)
     }
 }
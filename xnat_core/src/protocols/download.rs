@@ -0,0 +1,484 @@
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_core::Stream;
+use futures_util::{stream, StreamExt, TryStreamExt};
+use reqwest::{header::{CONTENT_RANGE, RANGE}, StatusCode};
+use tokio::fs::{self, File, OpenOptions};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use crate::client::{ClientCore, ClientREST, Xnat};
+use crate::models::{Resource, ResultSet};
+use crate::uri::data::resources::ResourcesUriBuilder;
+use crate::uri::data::{ExperimentUri, ProjectUriLegacy, SubjectUriLegacy};
+use crate::version::Version;
+use crate::{Error, UriBuilder};
+use super::archive::{enclosed, extract_zip, ZipLayout, ZipSource};
+use super::crud::try_retrieve;
+
+/// Stream of the bytes of a download.
+pub type ByteStream = Pin<Box<dyn Stream<Item = crate::Result<Bytes>> + Send>>;
+
+/// Progress of a file download, reported each
+/// time a chunk is written to disk.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DownloadProgress {
+    /// Where the file is being written.
+    pub file: PathBuf,
+    /// Number of bytes of the file written so
+    /// far, including any resumed from.
+    pub bytes_received: u64,
+    /// Number of bytes in the file, if known.
+    pub total_bytes: Option<u64>,
+}
+
+/// Function called with the progress of a
+/// download.
+type ProgressFn = Arc<dyn Fn(DownloadProgress) + Send + Sync>;
+
+/// Options which control how files are
+/// downloaded from a resource.
+///
+/// ```no_compile
+/// let options = DownloadOptions::default()
+///     .with_parallel(8)
+///     .with_verify(true);
+/// ```
+#[derive(Clone)]
+pub struct DownloadOptions {
+    attempts: u32,
+    parallel: usize,
+    progress: Option<ProgressFn>,
+    resume:   bool,
+    verify:   bool,
+}
+
+impl Default for DownloadOptions {
+    fn default() -> Self {
+        Self {
+            attempts: 3,
+            parallel: 4,
+            progress: None,
+            resume: true,
+            verify: false,
+        }
+    }
+}
+
+impl DownloadOptions {
+    /// Set the greatest number of times a file
+    /// is requested before its download fails.
+    pub fn with_attempts(mut self, value: u32) -> Self {
+        self.attempts = value.max(1);
+        self
+    }
+
+    /// Set the number of files of a resource
+    /// downloaded at once.
+    pub fn with_parallel(mut self, value: usize) -> Self {
+        self.parallel = value.max(1);
+        self
+    }
+
+    /// Call `callback` with the progress of each
+    /// file each time a chunk is written.
+    pub fn with_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(DownloadProgress) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// Whether an interrupted download continues
+    /// from the bytes already written, rather
+    /// than starting over.
+    pub fn with_resume(mut self, value: bool) -> Self {
+        self.resume = value;
+        self
+    }
+
+    /// Whether files are checked against the
+    /// digest the host reports for them.
+    pub fn with_verify(mut self, value: bool) -> Self {
+        self.verify = value;
+        self
+    }
+}
+
+impl Debug for DownloadOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DownloadOptions")
+            .field("attempts", &self.attempts)
+            .field("parallel", &self.parallel)
+            .field("progress", &self.progress.is_some())
+            .field("resume", &self.resume)
+            .field("verify", &self.verify)
+            .finish()
+    }
+}
+
+/// Type is able to download files from a
+/// resource.
+///
+/// A `Resource` which names a file, by its `uri`
+/// or `name`, refers to that file alone. One
+/// which names only a `collection` refers to
+/// every file of the resource.
+#[async_trait]
+pub trait Download {
+    /// Download a resource file to `path`, or
+    /// every file of a resource into `path` as a
+    /// directory. Returns the paths written.
+    ///
+    /// Files are written to a `.part` file first,
+    /// so that an interrupted download can be
+    /// resumed, and moved into place once they
+    /// are complete.
+    async fn download_to(&self, model: &Resource, path: &Path, options: &DownloadOptions) -> crate::Result<Vec<PathBuf>>;
+    /// Stream the contents of a resource file, or
+    /// every file of a resource as a zip archive.
+    async fn download_stream(&self, model: &Resource) -> crate::Result<ByteStream>;
+    /// Download the files of `source` as a zip
+    /// archive written to `path`.
+    async fn download_zip(&self, source: &ZipSource, path: &Path) -> crate::Result<()>;
+    /// Download the files of `source` as a zip
+    /// archive and extract them into `dir`, laid
+    /// out by `layout`. Returns the paths written.
+    async fn download_extracted(&self, source: &ZipSource, dir: &Path, layout: &ZipLayout) -> crate::Result<Vec<PathBuf>>;
+    /// List the files of a resource.
+    async fn list_files(&self, model: &Resource) -> crate::Result<Vec<Resource>>;
+}
+
+#[async_trait]
+impl<V> Download for Xnat<V>
+where
+    Self: ClientCore<Version = V> + ClientREST,
+    V: Version + ExperimentUri + ProjectUriLegacy + SubjectUriLegacy + Send + Sync,
+{
+    async fn download_to(&self, model: &Resource, path: &Path, options: &DownloadOptions) -> crate::Result<Vec<PathBuf>> {
+        if names_file(model) {
+            let uri = file_uri(self.version(), model)?;
+            let path = fetch_file(self, &uri, path, model.digest.as_deref(), options).await?;
+            return Ok(vec![path])
+        }
+
+        let mut files = Vec::new();
+        for file in self.list_files(model).await? {
+            let path = path.join(relative_path(&file)?);
+            files.push((file, path));
+        }
+        stream::iter(files)
+            .map(|(file, path)| async move {
+                let uri = file_uri(self.version(), &file)?;
+                fetch_file(self, &uri, &path, file.digest.as_deref(), options).await
+            })
+            .buffer_unordered(options.parallel)
+            .try_collect()
+            .await
+    }
+
+    async fn download_stream(&self, model: &Resource) -> crate::Result<ByteStream> {
+        let request = if names_file(model) {
+            self.get(&file_uri(self.version(), model)?).await?
+        } else {
+            let uri = resource_uri(self.version(), model)?;
+            self.get(&uri).await?.query(&[("format", "zip")])
+        };
+        let response = try_retrieve(self.execute(request).await?, |r| async { r }).await?;
+        Ok(Box::pin(response.bytes_stream().map_err(Error::from)))
+    }
+
+    async fn download_zip(&self, source: &ZipSource, path: &Path) -> crate::Result<()> {
+        let uri = source.files_uri(self.version())?;
+        let request = self.get(&uri).await?.query(&[("format", "zip")]);
+        let response = try_retrieve(self.execute(request).await?, |r| async { r }).await?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        let partial = partial_path(path);
+        let mut file = File::create(&partial).await?;
+        let mut body = response.bytes_stream();
+        while let Some(chunk) = body.next().await {
+            file.write_all(&chunk?).await?;
+        }
+        file.flush().await?;
+        fs::rename(&partial, path).await?;
+        Ok(())
+    }
+
+    async fn download_extracted(&self, source: &ZipSource, dir: &Path, layout: &ZipLayout) -> crate::Result<Vec<PathBuf>> {
+        let archive = dir.join(".oxinat-download.zip");
+        self.download_zip(source, &archive).await?;
+
+        let extract = {
+            let (archive, dir, layout) = (archive.to_owned(), dir.to_owned(), layout.to_owned());
+            move || extract_zip(&archive, &dir, &layout)
+        };
+        let extracted = tokio::task::spawn_blocking(extract)
+            .await
+            .map_err(std::io::Error::other)?;
+        fs::remove_file(&archive).await?;
+        extracted
+    }
+
+    async fn list_files(&self, model: &Resource) -> crate::Result<Vec<Resource>> {
+        let uri = resource_uri(self.version(), model)?;
+        let request = self.get(&uri).await?.query(&[("format", "json")]);
+        let response = try_retrieve(self.execute(request).await?, |r| async { r }).await?;
+        let files = response
+            .json::<ResultSet<Resource>>()
+            .await?
+            .data
+            .result
+            .into_iter()
+            .map(|mut f| {
+                f.project.clone_from(&model.project);
+                f.subject.clone_from(&model.subject);
+                f.experiment.clone_from(&model.experiment);
+                f.scan.clone_from(&model.scan);
+                f.collection.clone_from(&model.collection);
+                f
+            })
+            .collect();
+        Ok(files)
+    }
+}
+
+/// Whether the model refers to a single file
+/// rather than a whole resource.
+fn names_file(model: &Resource) -> bool {
+    model.uri.is_some() || model.name.is_some()
+}
+
+/// Build the URI listing the files of the
+/// resource a model refers to.
+pub(crate) fn resource_uri<V>(version: &V, model: &Resource) -> crate::Result<String>
+where
+    V: ExperimentUri + ProjectUriLegacy + SubjectUriLegacy,
+{
+    let parent = match model {
+        Resource {
+            project: Some(pjt),
+            subject: Some(sbj),
+            experiment: Some(exp),
+            scan: Some(scn),
+            ..
+        } => {
+            version
+                .project_data()
+                .with_id(pjt)
+                .subjects()
+                .with_subject(sbj)
+                .experiments()
+                .with_experiment(exp)
+                .scans()
+                .with_scan(scn)
+                .build()
+        },
+        Resource {
+            project: Some(pjt),
+            subject: Some(sbj),
+            experiment: Some(exp),
+            ..
+        } => {
+            version
+                .project_data()
+                .with_id(pjt)
+                .subjects()
+                .with_subject(sbj)
+                .experiments()
+                .with_experiment(exp)
+                .build()
+        },
+        Resource { project: Some(pjt), subject: Some(sbj), .. } => {
+            version.project_data().with_id(pjt).subjects().with_subject(sbj).build()
+        },
+        Resource { project: Some(pjt), .. } => version.project_data().with_id(pjt).build(),
+        Resource { subject: Some(sbj), .. } => version.subject_data().with_subject(sbj).build(),
+        Resource { experiment: Some(exp), scan: Some(scn), .. } => {
+            version
+                .experiment_data()
+                .with_experiment(exp)
+                .scans()
+                .with_scan(scn)
+                .build()
+        },
+        Resource { experiment: Some(exp), .. } => {
+            version.experiment_data().with_experiment(exp).build()
+        },
+        _ => return Err(Error::IdentifierRequired("any identifier".into()))
+    }?;
+    let collection = model
+        .collection
+        .as_ref()
+        .ok_or_else(|| Error::IdentifierRequired("collection".into()))?;
+    Ok(ResourcesUriBuilder::default().with_parent(&parent).with_resource(collection).files()?)
+}
+
+/// Build the URI of the file a model refers to,
+/// preferring the URI the host reported.
+fn file_uri<V>(version: &V, model: &Resource) -> crate::Result<String>
+where
+    V: ExperimentUri + ProjectUriLegacy + SubjectUriLegacy,
+{
+    if let Some(uri) = &model.uri {
+        return Ok(uri.to_owned())
+    }
+    let name = model
+        .name
+        .as_ref()
+        .ok_or_else(|| Error::IdentifierRequired("name".into()))?;
+    Ok(format!("{}/{name}", resource_uri(version, model)?))
+}
+
+/// Path of a file relative to its resource. Paths
+/// which would escape the destination are
+/// refused.
+fn relative_path(file: &Resource) -> crate::Result<PathBuf> {
+    let name = file
+        .uri
+        .as_deref()
+        .and_then(|u| u.split_once("/files/").map(|(_, p)| p))
+        .or(file.name.as_deref())
+        .ok_or_else(|| Error::IdentifierRequired("name".into()))?;
+    enclosed(name.as_ref()).ok_or_else(|| Error::UnsafePath(name.into()))
+}
+
+/// Where a file is written until its download
+/// is complete.
+fn partial_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_owned();
+    name.push(".part");
+    path.with_file_name(name)
+}
+
+/// Download the file at `uri` to `path`,
+/// resuming after interruptions, then verify it
+/// against `digest`.
+async fn fetch_file<C>(
+    client: &C,
+    uri: &str,
+    path: &Path,
+    digest: Option<&str>,
+    options: &DownloadOptions,
+) -> crate::Result<PathBuf>
+where
+    C: ClientREST + Sync,
+{
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).await?;
+    }
+    let partial = partial_path(path);
+
+    let mut attempt = 1;
+    loop {
+        let offset = match options.resume {
+            true => fs::metadata(&partial).await.map_or(0, |m| m.len()),
+            false => 0,
+        };
+        match fetch_range(client, uri, path, offset, options).await {
+            Ok(true) => break,
+            Ok(false) if attempt < options.attempts => attempt += 1,
+            Err(Error::Transport(e)) if attempt < options.attempts => {
+                log::warn!("download of `{uri}` interrupted ({e}), resuming");
+                attempt += 1;
+            },
+            Ok(false) => {
+                let message = format!("could not resume download of `{uri}`");
+                return Err(Error::Config(message))
+            },
+            Err(e) => return Err(e),
+        }
+    }
+
+    if let Some(expected) = digest.filter(|_| options.verify) {
+        let actual = md5_of(&partial).await?;
+        if !actual.eq_ignore_ascii_case(expected) {
+            fs::remove_file(&partial).await?;
+            return Err(Error::Checksum { path: path.to_owned(), expected: expected.to_owned(), actual })
+        }
+    }
+    fs::rename(&partial, path).await?;
+    Ok(path.to_owned())
+}
+
+/// Request the file at `uri` from `offset`,
+/// writing it to the partial file of `path`.
+/// Returns whether the file is complete, or must
+/// be started over.
+async fn fetch_range<C>(
+    client: &C,
+    uri: &str,
+    path: &Path,
+    offset: u64,
+    options: &DownloadOptions,
+) -> crate::Result<bool>
+where
+    C: ClientREST + Sync,
+{
+    let partial = partial_path(path);
+    let mut request = client.get(uri).await?;
+    if offset > 0 {
+        request = request.header(RANGE, format!("bytes={offset}-"));
+    }
+    let response = client.execute(request).await?;
+
+    if response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+        // What was written is either the whole file
+        // or does not belong to it.
+        let total = response
+            .headers()
+            .get(CONTENT_RANGE)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.rsplit('/').next()?.parse::<u64>().ok());
+        if total == Some(offset) {
+            return Ok(true)
+        }
+        fs::remove_file(&partial).await?;
+        return Ok(false)
+    }
+    let response = try_retrieve(response, |r| async { r }).await?;
+
+    let (mut file, mut received) = match response.status() {
+        StatusCode::PARTIAL_CONTENT => (OpenOptions::new().append(true).open(&partial).await?, offset),
+        _ => (File::create(&partial).await?, 0),
+    };
+    let total_bytes = response.content_length().map(|l| l + received);
+    let mut body = response.bytes_stream();
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk?;
+        file.write_all(&chunk).await?;
+        received += chunk.len() as u64;
+        if let Some(callback) = &options.progress {
+            callback(DownloadProgress {
+                file: path.to_owned(),
+                bytes_received: received,
+                total_bytes,
+            });
+        }
+    }
+    file.flush().await?;
+    Ok(true)
+}
+
+/// Compute the MD5 digest of a file as a
+/// hexadecimal string.
+async fn md5_of(path: &Path) -> crate::Result<String> {
+    let mut file = File::open(path).await?;
+    let mut context = md5::Context::new();
+    let mut buffer = vec![0; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer).await?;
+        if read == 0 {
+            break
+        }
+        context.consume(&buffer[..read]);
+    }
+    Ok(format!("{:x}", context.compute()))
+}
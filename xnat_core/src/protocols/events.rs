@@ -0,0 +1,185 @@
+use async_trait::async_trait;
+use reqwest::StatusCode;
+
+use crate::client::{ClientCore, ClientREST, Xnat};
+use crate::models::{
+    AvailableEvent,
+    DeliveredEvent,
+    DeliverySummary,
+    EventAction,
+    EventSubscription,
+};
+use crate::uri::admin::events::EventsUriBuilder;
+use crate::uri::admin::{DeliveredType, EventType, EventsUri, SubscriptionAction};
+use crate::version::Version;
+use crate::{Error, ErrorResponse};
+use super::crud::try_retrieve;
+use super::paging::{Page, Paging};
+
+/// Root of the event service endpoints, either
+/// site-wide or for one project.
+fn events_uri<V: EventsUri>(version: &V, project: Option<&str>) -> EventsUriBuilder<String> {
+    let events = version.events();
+    match project {
+        Some(project) => events.with_project(project),
+        None => events,
+    }
+}
+
+/// Type is able to manage subscriptions to the
+/// XNAT event service, either site-wide or for
+/// one project.
+#[async_trait]
+pub trait EventSubscriptions {
+    /// List the subscriptions of a project, or
+    /// the site-wide subscriptions if no project
+    /// is given.
+    async fn event_subscriptions(&self, project: Option<&str>) -> crate::Result<Vec<EventSubscription>>;
+    /// Get a subscription by its id.
+    async fn event_subscription(&self, project: Option<&str>, id: u64) -> crate::Result<EventSubscription>;
+    /// Create a subscription. Returns the
+    /// subscription as XNAT stored it, with its
+    /// id.
+    async fn create_subscription(&self, project: Option<&str>, subscription: &EventSubscription) -> crate::Result<EventSubscription>;
+    /// Check a subscription without creating it.
+    /// Fails with the reason XNAT gives if the
+    /// subscription is invalid.
+    async fn validate_subscription(&self, project: Option<&str>, subscription: &EventSubscription) -> crate::Result<()>;
+    /// Have a subscription carry out its action
+    /// on matching events again.
+    async fn activate_subscription(&self, project: Option<&str>, id: u64) -> crate::Result<()>;
+    /// Stop a subscription carrying out its action
+    /// without deleting it.
+    async fn deactivate_subscription(&self, project: Option<&str>, id: u64) -> crate::Result<()>;
+    /// Delete a subscription.
+    async fn delete_subscription(&self, project: Option<&str>, id: u64) -> crate::Result<()>;
+    /// List the types of event subscriptions can
+    /// filter on.
+    async fn available_events(&self, project: Option<&str>) -> crate::Result<Vec<AvailableEvent>>;
+    /// List the actions subscriptions can carry
+    /// out, optionally only those available to
+    /// one type of event.
+    async fn available_actions(&self, project: Option<&str>, event_type: Option<&str>) -> crate::Result<Vec<EventAction>>;
+    /// Get one window of the events delivered to
+    /// subscriptions, oldest first.
+    ///
+    /// XNAT reports the history in full; the
+    /// window is selected on the client.
+    async fn delivered_events(&self, project: Option<&str>, paging: &Paging) -> crate::Result<Page<DeliveredEvent>>;
+    /// Count the events delivered to
+    /// subscriptions by type, project, status and
+    /// subscription.
+    async fn delivery_summary(&self, project: Option<&str>) -> crate::Result<DeliverySummary>;
+    /// Count the events delivered to
+    /// subscriptions.
+    async fn delivery_count(&self, project: Option<&str>) -> crate::Result<u64>;
+}
+
+#[async_trait]
+impl<V> EventSubscriptions for Xnat<V>
+where
+    Self: ClientCore<Version = V> + ClientREST,
+    V: Version + EventsUri + Send + Sync,
+{
+    async fn event_subscriptions(&self, project: Option<&str>) -> crate::Result<Vec<EventSubscription>> {
+        let events = events_uri(self.version(), project);
+        let uri = events.subscription().with_action(SubscriptionAction::All);
+        let response = try_retrieve(self.execute(self.get(&uri).await?).await?, |r| async { r }).await?;
+        Ok(response.json().await?)
+    }
+
+    async fn event_subscription(&self, project: Option<&str>, id: u64) -> crate::Result<EventSubscription> {
+        let events = events_uri(self.version(), project);
+        let uri = events.subscription().with_id(id.to_string());
+        let response = try_retrieve(self.execute(self.get(&uri).await?).await?, |r| async { r }).await?;
+        Ok(response.json().await?)
+    }
+
+    async fn create_subscription(&self, project: Option<&str>, subscription: &EventSubscription) -> crate::Result<EventSubscription> {
+        let events = events_uri(self.version(), project);
+        let request = self.post(&events.subscription()).await?.json(subscription);
+        let response = try_retrieve(self.execute(request).await?, |r| async { r }).await?;
+
+        // Some versions of XNAT respond with only
+        // the name of the new subscription.
+        if let Ok(created) = serde_json::from_slice::<EventSubscription>(&response.bytes().await?) {
+            return Ok(created)
+        }
+        self.event_subscriptions(project)
+            .await?
+            .into_iter()
+            .rev()
+            .find(|s| s.name.is_some() && s.name == subscription.name)
+            .ok_or_else(|| Error::from_status(ErrorResponse::from_status(StatusCode::NOT_FOUND)))
+    }
+
+    async fn validate_subscription(&self, project: Option<&str>, subscription: &EventSubscription) -> crate::Result<()> {
+        let events = events_uri(self.version(), project);
+        let uri = events.subscription().with_action(SubscriptionAction::Validate);
+        let request = self.post(&uri).await?.json(subscription);
+        try_retrieve(self.execute(request).await?, |_| async {}).await
+    }
+
+    async fn activate_subscription(&self, project: Option<&str>, id: u64) -> crate::Result<()> {
+        let events = events_uri(self.version(), project);
+        let uri = events
+            .subscription()
+            .with_action(SubscriptionAction::Activate)
+            .with_id(id.to_string());
+        try_retrieve(self.execute(self.post(&uri).await?).await?, |_| async {}).await
+    }
+
+    async fn deactivate_subscription(&self, project: Option<&str>, id: u64) -> crate::Result<()> {
+        let events = events_uri(self.version(), project);
+        let uri = events
+            .subscription()
+            .with_action(SubscriptionAction::Deactivate)
+            .with_id(id.to_string());
+        try_retrieve(self.execute(self.post(&uri).await?).await?, |_| async {}).await
+    }
+
+    async fn delete_subscription(&self, project: Option<&str>, id: u64) -> crate::Result<()> {
+        let events = events_uri(self.version(), project);
+        let uri = events.subscription().with_id(id.to_string());
+        try_retrieve(self.execute(self.delete(&uri).await?).await?, |_| async {}).await
+    }
+
+    async fn available_events(&self, project: Option<&str>) -> crate::Result<Vec<AvailableEvent>> {
+        let events = events_uri(self.version(), project);
+        let response = try_retrieve(self.execute(self.get(&events.event()).await?).await?, |r| async { r }).await?;
+        Ok(response.json().await?)
+    }
+
+    async fn available_actions(&self, project: Option<&str>, event_type: Option<&str>) -> crate::Result<Vec<EventAction>> {
+        let events = events_uri(self.version(), project);
+        let request = match event_type {
+            Some(event_type) => {
+                let uri = events.actions().with_event_type(EventType::Multiple);
+                self.get(&uri).await?.query(&[("event-type", event_type)])
+            },
+            None => self.get(&events.actions()).await?,
+        };
+        let response = try_retrieve(self.execute(request).await?, |r| async { r }).await?;
+        Ok(response.json().await?)
+    }
+
+    async fn delivered_events(&self, project: Option<&str>, paging: &Paging) -> crate::Result<Page<DeliveredEvent>> {
+        let events = events_uri(self.version(), project);
+        let response = try_retrieve(self.execute(self.get(&events.delivered()).await?).await?, |r| async { r }).await?;
+        Ok(paging.apply(response.json().await?))
+    }
+
+    async fn delivery_summary(&self, project: Option<&str>) -> crate::Result<DeliverySummary> {
+        let events = events_uri(self.version(), project);
+        let uri = events.delivered().with_deliver_type(DeliveredType::Summary);
+        let response = try_retrieve(self.execute(self.get(&uri).await?).await?, |r| async { r }).await?;
+        Ok(response.json().await?)
+    }
+
+    async fn delivery_count(&self, project: Option<&str>) -> crate::Result<u64> {
+        let events = events_uri(self.version(), project);
+        let uri = events.delivered().with_deliver_type(DeliveredType::Count);
+        let response = try_retrieve(self.execute(self.get(&uri).await?).await?, |r| async { r }).await?;
+        Ok(response.json().await?)
+    }
+}
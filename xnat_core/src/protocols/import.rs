@@ -0,0 +1,283 @@
+use std::path::Path;
+use std::str::FromStr;
+
+use async_trait::async_trait;
+use reqwest::{header::{CONTENT_LENGTH, CONTENT_TYPE}, multipart::{Form, Part}, Body, Response};
+use serde::Serialize;
+use tokio::io::AsyncRead;
+use tokio_util::io::ReaderStream;
+
+use crate::client::{ClientCore, ClientREST, Xnat};
+use crate::uri::data::ArchiveUri;
+use crate::version::Version;
+use crate::Error;
+use super::crud::try_retrieve;
+use super::upload::{file_name, InBody};
+
+/// Importer XNAT processes uploaded files with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum ImportHandler {
+    /// A zip or tar archive of DICOM files.
+    #[serde(rename = "DICOM-zip")]
+    DicomZip,
+    /// DICOM files sent one at a time, as a
+    /// DICOM C-STORE would.
+    #[serde(rename = "gradual-DICOM")]
+    GradualDicom,
+    /// A zip of a session already laid out as
+    /// XNAT archives it.
+    #[serde(rename = "SI")]
+    SessionImporter,
+}
+
+/// Where imported sessions are placed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum ImportDestination {
+    #[serde(rename = "/archive")]
+    Archive,
+    #[serde(rename = "/prearchive")]
+    Prearchive,
+}
+
+/// How files of a session which already exists
+/// are treated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportOverwrite {
+    /// Add new files to the session.
+    Append,
+    /// Replace the session entirely.
+    Delete,
+    /// Refuse to import into the session.
+    None,
+}
+
+/// Options for the XNAT importer.
+///
+/// ```no_compile
+/// let options = ImportOptions::default()
+///     .with_handler(ImportHandler::DicomZip)
+///     .with_project("PROJECT_A")
+///     .with_subject("SUBJECT_A")
+///     .with_experiment("SESSION_A")
+///     .with_destination(ImportDestination::Archive);
+/// ```
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ImportOptions {
+    #[serde(rename = "auto-archive", skip_serializing_if = "Option::is_none")]
+    auto_archive: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    dest: Option<ImportDestination>,
+    #[serde(rename = "Direct-Archive", skip_serializing_if = "Option::is_none")]
+    direct_archive: Option<bool>,
+    #[serde(rename = "EXPT_LABEL", skip_serializing_if = "Option::is_none")]
+    experiment: Option<String>,
+    #[serde(rename = "import-handler", skip_serializing_if = "Option::is_none")]
+    handler: Option<ImportHandler>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    overwrite: Option<ImportOverwrite>,
+    #[serde(rename = "PROJECT_ID", skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+    #[serde(rename = "SUBJECT_ID", skip_serializing_if = "Option::is_none")]
+    subject: Option<String>,
+    #[serde(rename = "http-session-listener", skip_serializing_if = "Option::is_none")]
+    upload_id: Option<String>,
+}
+
+impl ImportOptions {
+    /// Whether sessions are archived as soon as
+    /// they are received, rather than held in the
+    /// prearchive.
+    pub fn with_auto_archive(mut self, value: bool) -> Self {
+        self.auto_archive = Some(value);
+        self
+    }
+
+    /// Set where imported sessions are placed.
+    pub fn with_destination(mut self, value: ImportDestination) -> Self {
+        self.dest = Some(value);
+        self
+    }
+
+    /// Whether sessions skip the prearchive and
+    /// are written into the archive directly.
+    pub fn with_direct_archive(mut self, value: bool) -> Self {
+        self.direct_archive = Some(value);
+        self
+    }
+
+    /// Set the label of the imported session.
+    pub fn with_experiment(mut self, label: &str) -> Self {
+        self.experiment = Some(label.to_owned());
+        self
+    }
+
+    /// Set the importer files are processed with.
+    pub fn with_handler(mut self, value: ImportHandler) -> Self {
+        self.handler = Some(value);
+        self
+    }
+
+    /// Set how files of an existing session are
+    /// treated.
+    pub fn with_overwrite(mut self, value: ImportOverwrite) -> Self {
+        self.overwrite = Some(value);
+        self
+    }
+
+    /// Set the project sessions are imported
+    /// into.
+    pub fn with_project(mut self, id: &str) -> Self {
+        self.project = Some(id.to_owned());
+        self
+    }
+
+    /// Set the subject sessions are imported
+    /// into.
+    pub fn with_subject(mut self, id: &str) -> Self {
+        self.subject = Some(id.to_owned());
+        self
+    }
+
+    /// Set the identifier the import's status is
+    /// tracked by. See `new_upload_id`.
+    pub fn with_upload_id(mut self, id: &str) -> Self {
+        self.upload_id = Some(id.to_owned());
+        self
+    }
+}
+
+/// Where the importer placed a session.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ImportLocation {
+    /// A session in the archive.
+    Archive {
+        project:    String,
+        subject:    String,
+        experiment: String,
+    },
+    /// A session awaiting review in the
+    /// prearchive.
+    Prearchive {
+        project:   String,
+        timestamp: String,
+        session:   String,
+    },
+}
+
+impl FromStr for ImportLocation {
+    type Err = String;
+
+    /// Parse the path the importer responds with,
+    /// such as
+    /// `/data/prearchive/projects/{project}/{timestamp}/{session}`
+    /// or
+    /// `/data/archive/projects/{project}/subjects/{subject}/experiments/{experiment}`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let path = s.trim();
+        let segments = |marker: &str| {
+            path.split_once(marker).map(|(_, rest)| {
+                rest.trim_end_matches('/').split('/').map(str::to_owned).collect::<Vec<_>>()
+            })
+        };
+
+        if let Some(segments) = segments("/prearchive/projects/") {
+            if let [project, timestamp, session] = &segments[..] {
+                return Ok(Self::Prearchive {
+                    project: project.to_owned(),
+                    timestamp: timestamp.to_owned(),
+                    session: session.to_owned(),
+                })
+            }
+        }
+        if let Some(segments) = segments("/archive/projects/") {
+            if let [project, s, subject, e, experiment] = &segments[..] {
+                if s == "subjects" && e == "experiments" {
+                    return Ok(Self::Archive {
+                        project: project.to_owned(),
+                        subject: subject.to_owned(),
+                        experiment: experiment.to_owned(),
+                    })
+                }
+            }
+        }
+        Err(format!("unrecognized import location `{path}`"))
+    }
+}
+
+/// Read the location of the imported session
+/// from the importer's response.
+pub(super) async fn import_location(response: Response) -> crate::Result<ImportLocation> {
+    let url = response.url().to_owned();
+    let body = response.text().await?;
+    body
+        .lines()
+        .find(|l| !l.trim().is_empty())
+        .unwrap_or_default()
+        .parse()
+        .map_err(|e: String| Error::Deserialization { url: Some(url), source: e.into() })
+}
+
+/// Type is able to import sessions of DICOM
+/// files through the XNAT importer.
+#[async_trait]
+pub trait Import {
+    /// Import a zip or tar archive of files,
+    /// sent as a multipart upload.
+    async fn import_archive(&self, path: &Path, options: &ImportOptions) -> crate::Result<ImportLocation>;
+    /// Import a single file, sent as the body of
+    /// the request.
+    async fn import_file(&self, path: &Path, options: &ImportOptions) -> crate::Result<ImportLocation> {
+        let file = tokio::fs::File::open(path).await?;
+        let length = file.metadata().await?.len();
+        self.import_reader(file, Some(length), options).await
+    }
+    /// Import the contents of a reader, sent as
+    /// the body of the request.
+    async fn import_reader<R>(&self, reader: R, length: Option<u64>, options: &ImportOptions) -> crate::Result<ImportLocation>
+    where
+        R: AsyncRead + Send + Sync + Unpin + 'static;
+}
+
+#[async_trait]
+impl<V> Import for Xnat<V>
+where
+    Self: ClientCore<Version = V> + ClientREST,
+    V: Version + ArchiveUri + Send + Sync,
+{
+    async fn import_archive(&self, path: &Path, options: &ImportOptions) -> crate::Result<ImportLocation> {
+        let name = file_name(path)?;
+        let file = tokio::fs::File::open(path).await?;
+        let length = file.metadata().await?.len();
+        let part = Part::stream_with_length(Body::wrap_stream(ReaderStream::new(file)), length)
+            .file_name(name);
+
+        let uri = self.version().archive_data().services().build_import()?;
+        let request = self
+            .post(&uri)
+            .await?
+            .query(options)
+            .multipart(Form::new().part("file", part));
+        let response = try_retrieve(self.execute(request).await?, |r| async { r }).await?;
+        import_location(response).await
+    }
+
+    async fn import_reader<R>(&self, reader: R, length: Option<u64>, options: &ImportOptions) -> crate::Result<ImportLocation>
+    where
+        R: AsyncRead + Send + Sync + Unpin + 'static,
+    {
+        let uri = self.version().archive_data().services().build_import()?;
+        let mut request = self
+            .post(&uri)
+            .await?
+            .query(options)
+            .query(&InBody { inbody: true })
+            .header(CONTENT_TYPE, "application/octet-stream")
+            .body(Body::wrap_stream(ReaderStream::new(reader)));
+        if let Some(length) = length {
+            request = request.header(CONTENT_LENGTH, length);
+        }
+        let response = try_retrieve(self.execute(request).await?, |r| async { r }).await?;
+        import_location(response).await
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+
+use crate::client::{ClientCore, ClientREST, Xnat};
+use crate::models::{ProjectGroup, ProjectMember, ResultSet};
+use crate::uri::data::ProjectUriLegacy;
+use crate::version::Version;
+use crate::Error;
+use super::crud::try_retrieve;
+
+/// A change made to the members of a project.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MembershipChange {
+    Added {
+        username: String,
+        group:    ProjectGroup,
+    },
+    Moved {
+        username: String,
+        from:     ProjectGroup,
+        to:       ProjectGroup,
+    },
+    Removed {
+        username: String,
+        group:    ProjectGroup,
+    },
+}
+
+/// Type is able to manage which users are members
+/// of a project, and in which group.
+#[async_trait]
+pub trait Membership {
+    /// List the members of a project.
+    async fn project_members(&self, project: &str) -> crate::Result<Vec<ProjectMember>>;
+    /// Add a user to a group of a project.
+    async fn add_member(&self, project: &str, username: &str, group: &ProjectGroup) -> crate::Result<()>;
+    /// Move a member of a project into another
+    /// group. XNAT removes them from the group
+    /// they were in.
+    async fn move_member(&self, project: &str, username: &str, group: &ProjectGroup) -> crate::Result<()> {
+        self.add_member(project, username, group).await
+    }
+    /// Remove a member from a group of a project.
+    async fn remove_member(&self, project: &str, username: &str, group: &ProjectGroup) -> crate::Result<()>;
+    /// Bring the members of a project in line with
+    /// `members`, adding users and moving them
+    /// between groups as needed. Members not in
+    /// the list are removed only if `prune` is
+    /// set. Returns the changes made.
+    ///
+    /// Stops at the first change XNAT refuses;
+    /// changes made before it are kept.
+    async fn sync_members(&self, project: &str, members: &[ProjectMember], prune: bool) -> crate::Result<Vec<MembershipChange>>
+    where
+        Self: Sync,
+    {
+        let desired = members
+            .iter()
+            .map(|m| {
+                let username = m.username.to_owned().ok_or_else(|| Error::IdentifierRequired("username".into()))?;
+                let group = m.group.to_owned().ok_or_else(|| Error::IdentifierRequired("group".into()))?;
+                Ok((username, group))
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+        let mut current = self
+            .project_members(project)
+            .await?
+            .into_iter()
+            .filter_map(|m| Some((m.username?, m.group?)))
+            .collect::<HashMap<_, _>>();
+
+        let mut changes = Vec::new();
+        for (username, group) in desired {
+            match current.remove(&username) {
+                Some(from) if from == group => continue,
+                Some(from) => {
+                    self.move_member(project, &username, &group).await?;
+                    changes.push(MembershipChange::Moved { username, from, to: group });
+                },
+                None => {
+                    self.add_member(project, &username, &group).await?;
+                    changes.push(MembershipChange::Added { username, group });
+                },
+            }
+        }
+
+        if prune {
+            let mut unlisted = current.into_iter().collect::<Vec<_>>();
+            unlisted.sort_by(|a, b| a.0.cmp(&b.0));
+            for (username, group) in unlisted {
+                self.remove_member(project, &username, &group).await?;
+                changes.push(MembershipChange::Removed { username, group });
+            }
+        }
+        Ok(changes)
+    }
+}
+
+#[async_trait]
+impl<V> Membership for Xnat<V>
+where
+    Self: ClientCore<Version = V> + ClientREST,
+    V: Version + ProjectUriLegacy + Send + Sync,
+{
+    async fn project_members(&self, project: &str) -> crate::Result<Vec<ProjectMember>> {
+        let projects = self.version().project_data().with_id(project);
+        let request = self.get(&projects.users()).await?.query(&[("format", "json")]);
+        let response = try_retrieve(self.execute(request).await?, |r| async { r }).await?;
+        let members = response
+            .json::<ResultSet<ProjectMember>>()
+            .await?
+            .data
+            .result
+            .into_iter()
+            .map(|mut m| {
+                m.project = Some(project.to_owned());
+                m
+            })
+            .collect();
+        Ok(members)
+    }
+
+    async fn add_member(&self, project: &str, username: &str, group: &ProjectGroup) -> crate::Result<()> {
+        let projects = self.version().project_data().with_id(project);
+        let uri = projects.users().with_group_name(group.to_string()).with_username(username);
+        try_retrieve(self.execute(self.put(&uri).await?).await?, |_| async {}).await
+    }
+
+    async fn remove_member(&self, project: &str, username: &str, group: &ProjectGroup) -> crate::Result<()> {
+        let projects = self.version().project_data().with_id(project);
+        let uri = projects.users().with_group_name(group.to_string()).with_username(username);
+        try_retrieve(self.execute(self.delete(&uri).await?).await?, |_| async {}).await
+    }
+}
@@ -1,12 +1,46 @@
+mod archive;
 mod create;
 mod crud;
 mod delete;
+mod download;
+mod events;
+mod import;
+mod membership;
+mod paging;
+mod prearchive;
+mod query;
 mod retrieve;
+mod share;
+mod status;
+mod tokens;
+mod update;
+mod upload;
+mod users;
 
+pub use archive::{extract_zip, ZipLayout, ZipSource};
 pub use crud::{
-    CrudError,
     Create,
     Retrieve,
     Update,
     Delete
 };
+pub use download::{ByteStream, Download, DownloadOptions, DownloadProgress};
+pub use events::EventSubscriptions;
+pub use import::{Import, ImportDestination, ImportHandler, ImportLocation, ImportOptions, ImportOverwrite};
+pub use membership::{Membership, MembershipChange};
+pub use paging::{Page, Paging, ResultStream, Rows};
+pub use prearchive::{ArchiveOptions, Prearchive};
+pub use query::{DateRange, Query};
+pub use share::Share;
+pub use status::{
+    new_upload_id,
+    StatusKind,
+    StatusMessage,
+    TrackUpload,
+    UploadHandle,
+    UploadState,
+    UploadStatus,
+};
+pub use tokens::AliasTokens;
+pub use upload::{Upload, UploadOptions, UploadProgress};
+pub use users::UserAdmin;
@@ -0,0 +1,257 @@
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_core::Stream;
+use serde::Serialize;
+
+use crate::models::ResultSet;
+use super::crud::PinnedFuture;
+
+/// Listing parameters which select a window of
+/// the records matching a request.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct Paging {
+    /// Number of matching records to skip.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    /// Greatest number of records to return.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+}
+
+impl Paging {
+    /// Select the records following `offset`, at
+    /// most `limit` of them.
+    pub fn new(offset: u64, limit: u64) -> Self {
+        Self { offset: Some(offset), limit: Some(limit) }
+    }
+
+    /// Select this window from records the host
+    /// returned in full.
+    pub fn apply<M>(&self, results: Vec<M>) -> Page<M> {
+        let total_records = Some(results.len() as u64);
+        let offset = self.offset.unwrap_or_default();
+        let results = self.window(results);
+        Page {
+            offset,
+            scanned: results.len() as u64,
+            results,
+            total_records,
+            host_records: total_records,
+        }
+    }
+
+    /// Select this window from a listing.
+    fn window<M>(&self, results: Vec<M>) -> Vec<M> {
+        results
+            .into_iter()
+            .skip(self.offset.unwrap_or_default() as usize)
+            .take(self.limit.map_or(usize::MAX, |l| l as usize))
+            .collect()
+    }
+}
+
+/// One window of the records matching a request.
+#[derive(Clone, Debug)]
+pub struct Page<M> {
+    /// Position of the window among all records
+    /// the host holds for the request.
+    pub offset: u64,
+    /// Number of records the host gave within
+    /// the window, including any then filtered
+    /// out on the client.
+    pub scanned: u64,
+    /// Records within this window.
+    pub results: Vec<M>,
+    /// Number of records matching the request,
+    /// if known. Unknown when records the host
+    /// counted were filtered out on the client.
+    pub total_records: Option<u64>,
+    /// Number of records the host holds for the
+    /// request, if it reported it.
+    pub host_records: Option<u64>,
+}
+
+impl<M> Page<M> {
+    /// Construct a page from a `ResultSet`
+    /// requested with the given `Paging`.
+    pub fn from_result_set(result_set: ResultSet<M>, paging: &Paging) -> Self {
+        let mut host_records = result_set.total_records();
+        let mut results = result_set.data.result;
+
+        // Hosts which ignore paging return every
+        // record, so the window is selected here
+        // instead.
+        if paging.limit.is_some_and(|l| results.len() as u64 > l) {
+            host_records = Some(results.len() as u64);
+            results = paging.window(results);
+        }
+        Self {
+            offset: paging.offset.unwrap_or_default(),
+            scanned: results.len() as u64,
+            results,
+            total_records: host_records,
+            host_records,
+        }
+    }
+
+    /// Consume this page, returning its records.
+    pub fn into_results(self) -> Vec<M> {
+        self.results
+    }
+
+    /// Convert each record of this page, failing
+    /// on the first which cannot be converted.
+    pub(crate) fn try_map<T, E, F>(self, f: F) -> Result<Page<T>, E>
+    where
+        F: FnMut(M) -> Result<T, E>,
+    {
+        Ok(Page {
+            offset: self.offset,
+            scanned: self.scanned,
+            results: self.results.into_iter().map(f).collect::<Result<_, _>>()?,
+            total_records: self.total_records,
+            host_records: self.host_records,
+        })
+    }
+}
+
+/// Records the host returned for a listing.
+#[derive(Clone, Debug)]
+pub enum Rows<M> {
+    /// One window of the listing, selected by
+    /// the host.
+    Page(Page<M>),
+    /// The whole listing, from endpoints which
+    /// cannot page it.
+    All(Vec<M>),
+}
+
+/// Function which requests the page selected by
+/// some `Paging`.
+type FetchPage<'a, M> = Box<dyn FnMut(Paging) -> PinnedFuture<'a, Page<M>> + Send + 'a>;
+
+/// Stream of records requested from the host one
+/// page at a time. Only one page is held in
+/// memory at once.
+pub struct ResultStream<'a, M> {
+    buffer:        VecDeque<M>,
+    exhausted:     bool,
+    fetch:         FetchPage<'a, M>,
+    filtered:      bool,
+    host_offset:   u64,
+    host_records:  Option<u64>,
+    page_size:     u64,
+    pending:       Option<PinnedFuture<'a, Page<M>>>,
+    position:      u64,
+    total_records: Option<u64>,
+}
+
+impl<'a, M> ResultStream<'a, M> {
+    /// Stream the records returned by `fetch`,
+    /// requesting `page_size` records at a time.
+    pub fn new<F>(page_size: u64, fetch: F) -> Self
+    where
+        F: FnMut(Paging) -> PinnedFuture<'a, Page<M>> + Send + 'a,
+    {
+        Self {
+            buffer: VecDeque::new(),
+            exhausted: false,
+            fetch: Box::new(fetch),
+            filtered: false,
+            host_offset: 0,
+            host_records: None,
+            page_size: page_size.max(1),
+            pending: None,
+            position: 0,
+            total_records: None,
+        }
+    }
+
+    /// Number of records yielded so far.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Number of records matching the request, if
+    /// known. Known once the first page is
+    /// received, unless records are filtered out
+    /// on the client, in which case it is known
+    /// once the last page is received.
+    pub fn total_records(&self) -> Option<u64> {
+        self.total_records
+    }
+
+    /// Take in a page received from the host.
+    fn receive(&mut self, page: Page<M>) {
+        // Pages advance by the records the host
+        // gave, not those left after filtering.
+        self.host_offset = page.offset + page.scanned;
+        self.host_records = page.host_records.or(self.host_records);
+        self.filtered |= (page.results.len() as u64) < page.scanned;
+
+        // Some endpoints ignore paging entirely, in
+        // which case the first page is everything.
+        self.exhausted = page.scanned < self.page_size
+            || self.host_records.is_some_and(|t| self.host_offset >= t);
+        self.buffer.extend(page.results);
+
+        self.total_records = if !self.filtered {
+            page.total_records.or(self.total_records)
+        } else if self.exhausted {
+            Some(self.position + self.buffer.len() as u64)
+        } else {
+            None
+        };
+    }
+}
+
+impl<M> Unpin for ResultStream<'_, M> {}
+
+impl<M> Stream for ResultStream<'_, M> {
+    type Item = crate::Result<M>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(model) = this.buffer.pop_front() {
+                this.position += 1;
+                return Poll::Ready(Some(Ok(model)))
+            }
+            if this.exhausted {
+                return Poll::Ready(None)
+            }
+
+            let offset = this.host_offset;
+            let limit = this.page_size;
+            let fetch = &mut this.fetch;
+            let pending = this
+                .pending
+                .get_or_insert_with(|| fetch(Paging::new(offset, limit)));
+            let Poll::Ready(page) = pending.as_mut().poll(cx) else {
+                return Poll::Pending
+            };
+            this.pending = None;
+
+            match page {
+                Ok(page) => this.receive(page),
+                Err(e) => {
+                    this.exhausted = true;
+                    return Poll::Ready(Some(Err(e)))
+                },
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.buffer.len();
+        if self.exhausted {
+            return (buffered, Some(buffered))
+        }
+        let remaining = self
+            .total_records
+            .map(|t| t.saturating_sub(self.position) as usize);
+        (buffered, remaining)
+    }
+}
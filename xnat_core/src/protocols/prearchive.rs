@@ -0,0 +1,198 @@
+use async_trait::async_trait;
+use reqwest::StatusCode;
+use serde::Serialize;
+
+use crate::client::{ClientCore, ClientREST, Xnat};
+use crate::models::{PrearchiveSession, ResultSet, Scan};
+use crate::uri::data::ArchiveUri;
+use crate::version::Version;
+use crate::{Error, ErrorResponse};
+use super::crud::try_retrieve;
+use super::import::{import_location, ImportLocation, ImportOverwrite};
+
+/// Options for moving a session out of the
+/// prearchive and into the archive.
+///
+/// The project and subject default to those of
+/// the session, and the label to the name XNAT
+/// gave the session.
+///
+/// ```no_compile
+/// let options = ArchiveOptions::default()
+///     .with_subject("SUBJECT_A")
+///     .with_label("SESSION_A")
+///     .with_overwrite(ImportOverwrite::Append);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ArchiveOptions {
+    label:     Option<String>,
+    overwrite: Option<ImportOverwrite>,
+    project:   Option<String>,
+    subject:   Option<String>,
+}
+
+impl ArchiveOptions {
+    /// Set the label the session is archived
+    /// under.
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_owned());
+        self
+    }
+
+    /// Set how files of an existing session are
+    /// treated.
+    pub fn with_overwrite(mut self, value: ImportOverwrite) -> Self {
+        self.overwrite = Some(value);
+        self
+    }
+
+    /// Set the project the session is archived
+    /// into.
+    pub fn with_project(mut self, id: &str) -> Self {
+        self.project = Some(id.to_owned());
+        self
+    }
+
+    /// Set the subject the session is archived
+    /// into.
+    pub fn with_subject(mut self, id: &str) -> Self {
+        self.subject = Some(id.to_owned());
+        self
+    }
+
+    /// Path in the archive the session is written
+    /// to, such as
+    /// `/archive/projects/{project}/subjects/{subject}/experiments/{label}`.
+    fn dest(&self, session: &PrearchiveSession) -> crate::Result<String> {
+        let project = self
+            .project
+            .as_ref()
+            .or(session.project.as_ref())
+            .ok_or_else(|| Error::IdentifierRequired("project id".into()))?;
+        let subject = self.subject.as_ref().or(session.subject.as_ref());
+
+        let mut dest = format!("/archive/projects/{project}");
+        match (subject, &self.label) {
+            (Some(subject), Some(label)) => dest += &format!("/subjects/{subject}/experiments/{label}"),
+            (Some(subject), None) => dest += &format!("/subjects/{subject}"),
+            (None, Some(_)) => return Err(Error::IdentifierRequired("subject id".into())),
+            (None, None) => (),
+        }
+        Ok(dest)
+    }
+}
+
+/// Parameters naming the session a prearchive
+/// service acts on.
+#[derive(Serialize)]
+struct Source<'a> {
+    src: &'a str,
+}
+
+/// Type is able to triage sessions held in the
+/// prearchive.
+#[async_trait]
+pub trait Prearchive {
+    /// List the sessions in the prearchive of a
+    /// project, or of every project if none is
+    /// given.
+    async fn prearchive_sessions(&self, project: Option<&str>) -> crate::Result<Vec<PrearchiveSession>>;
+    /// Get a session from the prearchive, with
+    /// its `scan_count` filled in.
+    async fn prearchive_session(&self, session: &PrearchiveSession) -> crate::Result<PrearchiveSession>;
+    /// List the scans of a session in the
+    /// prearchive.
+    async fn prearchive_scans(&self, session: &PrearchiveSession) -> crate::Result<Vec<Scan>>;
+    /// Have XNAT rebuild a session from the files
+    /// it received.
+    async fn rebuild_session(&self, session: &PrearchiveSession) -> crate::Result<()>;
+    /// Move a session into the prearchive of
+    /// another project.
+    async fn move_session(&self, session: &PrearchiveSession, project: &str) -> crate::Result<()>;
+    /// Move a session into the archive. Returns
+    /// where it was archived.
+    async fn archive_session(&self, session: &PrearchiveSession, options: &ArchiveOptions) -> crate::Result<ImportLocation>;
+    /// Delete a session from the prearchive.
+    async fn delete_session(&self, session: &PrearchiveSession) -> crate::Result<()>;
+}
+
+#[async_trait]
+impl<V> Prearchive for Xnat<V>
+where
+    Self: ClientCore<Version = V> + ClientREST,
+    V: Version + ArchiveUri + Send + Sync,
+{
+    async fn prearchive_sessions(&self, project: Option<&str>) -> crate::Result<Vec<PrearchiveSession>> {
+        let prearchive = self.version().archive_data().prearchive();
+        let mut uri = prearchive.projects();
+        if let Some(project) = project {
+            uri = uri.with_project(project);
+        }
+        let request = self.get(&uri).await?.query(&[("format", "json")]);
+        let response = try_retrieve(self.execute(request).await?, |r| async { r }).await?;
+        Ok(response.json::<ResultSet<PrearchiveSession>>().await?.data.result)
+    }
+
+    async fn prearchive_session(&self, session: &PrearchiveSession) -> crate::Result<PrearchiveSession> {
+        let (project, timestamp, folder) = session.identifiers()?;
+        let mut found = self
+            .prearchive_sessions(Some(project))
+            .await?
+            .into_iter()
+            .find(|s| {
+                s.timestamp.as_deref() == Some(timestamp)
+                    && s.folder_name.as_deref().or(s.name.as_deref()) == Some(folder)
+            })
+            .ok_or_else(|| Error::from_status(ErrorResponse::from_status(StatusCode::NOT_FOUND)))?;
+
+        found.scan_count = Some(self.prearchive_scans(&found).await?.len() as u64);
+        Ok(found)
+    }
+
+    async fn prearchive_scans(&self, session: &PrearchiveSession) -> crate::Result<Vec<Scan>> {
+        let (project, timestamp, folder) = session.identifiers()?;
+        let prearchive = self.version().archive_data().prearchive();
+        let projects = prearchive.projects().with_project(project);
+        let uri = projects.scans().with_timestamp(timestamp).with_experiment(folder);
+
+        let request = self.get(&uri).await?.query(&[("format", "json")]);
+        let response = try_retrieve(self.execute(request).await?, |r| async { r }).await?;
+        Ok(response.json::<ResultSet<Scan>>().await?.data.result)
+    }
+
+    async fn rebuild_session(&self, session: &PrearchiveSession) -> crate::Result<()> {
+        let uri = self.version().archive_data().prearchive().build_rebuild()?;
+        let request = self.post(&uri).await?.query(&Source { src: &session.src()? });
+        try_retrieve(self.execute(request).await?, |_| async {}).await
+    }
+
+    async fn move_session(&self, session: &PrearchiveSession, project: &str) -> crate::Result<()> {
+        let uri = self.version().archive_data().prearchive().build_move()?;
+        let request = self
+            .post(&uri)
+            .await?
+            .query(&Source { src: &session.src()? })
+            .query(&[("newProject", project), ("async", "false")]);
+        try_retrieve(self.execute(request).await?, |_| async {}).await
+    }
+
+    async fn archive_session(&self, session: &PrearchiveSession, options: &ArchiveOptions) -> crate::Result<ImportLocation> {
+        let uri = self.version().archive_data().services().build_archive()?;
+        let mut request = self
+            .post(&uri)
+            .await?
+            .query(&Source { src: &session.src()? })
+            .query(&[("dest", options.dest(session)?)]);
+        if let Some(overwrite) = &options.overwrite {
+            request = request.query(&[("overwrite", overwrite)]);
+        }
+        let response = try_retrieve(self.execute(request).await?, |r| async { r }).await?;
+        import_location(response).await
+    }
+
+    async fn delete_session(&self, session: &PrearchiveSession) -> crate::Result<()> {
+        let uri = self.version().archive_data().prearchive().build_delete()?;
+        let request = self.post(&uri).await?.query(&Source { src: &session.src()? });
+        try_retrieve(self.execute(request).await?, |_| async {}).await
+    }
+}
@@ -0,0 +1,353 @@
+use std::cmp::Ordering;
+use std::ops::{Bound, RangeBounds};
+
+use chrono::{Days, NaiveDate};
+use serde::{ser::SerializeMap, Serialize, Serializer};
+use serde_json::Value;
+
+use super::paging::{Page, Paging};
+
+/// Inclusive range of dates a listing is
+/// filtered by. Either end may be open.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DateRange {
+    from: Option<NaiveDate>,
+    to:   Option<NaiveDate>,
+}
+
+impl DateRange {
+    /// Whether the date falls within this range.
+    pub fn contains(&self, date: &NaiveDate) -> bool {
+        self.from.is_none_or(|f| f <= *date) && self.to.is_none_or(|t| *date <= t)
+    }
+
+    /// Earliest date within this range.
+    pub fn from(&self) -> Option<NaiveDate> {
+        self.from
+    }
+
+    /// Latest date within this range.
+    pub fn to(&self) -> Option<NaiveDate> {
+        self.to
+    }
+
+    /// The range in the form XNAT expects,
+    /// `MM/DD/YYYY-MM/DD/YYYY`. Only closed
+    /// ranges can be sent to the host.
+    fn to_param(self) -> Option<String> {
+        let fmt = "%m/%d/%Y";
+        Some(format!("{}-{}", self.from?.format(fmt), self.to?.format(fmt)))
+    }
+}
+
+impl<R: RangeBounds<NaiveDate>> From<R> for DateRange {
+    fn from(value: R) -> Self {
+        let from = match value.start_bound() {
+            Bound::Included(d) => Some(*d),
+            Bound::Excluded(d) => d.checked_add_days(Days::new(1)),
+            Bound::Unbounded => None,
+        };
+        let to = match value.end_bound() {
+            Bound::Included(d) => Some(*d),
+            Bound::Excluded(d) => d.checked_sub_days(Days::new(1)),
+            Bound::Unbounded => None,
+        };
+        Self { from, to }
+    }
+}
+
+/// Filters, sorting and paging applied to a
+/// listing of some model `M`, in addition to the
+/// fields set on the model itself.
+///
+/// Parameters are sent to the host, and filters
+/// are checked again against the records it
+/// returns so that endpoints which ignore them
+/// still give the expected results.
+///
+/// ```no_compile
+/// use oxinat_core::protocols::{Query, Retrieve};
+///
+/// let query = Query::new(Experiment::default())
+///     .with_xsi_type("xnat:mrSessionData")
+///     .with_label("*_MR1")
+///     .with_date(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()..)
+///     .with_sort_by("date")
+///     .with_limit(100);
+/// let page = client.query(&query).await?;
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Query<M> {
+    columns:     Vec<String>,
+    date:        Option<DateRange>,
+    descending:  bool,
+    insert_date: Option<DateRange>,
+    label:       Option<String>,
+    model:       M,
+    paging:      Paging,
+    sort_by:     Option<String>,
+    xsi_type:    Option<String>,
+}
+
+impl<M> Query<M> {
+    /// Query for instances which match the fields
+    /// set on `model`.
+    pub fn new(model: M) -> Self {
+        Self {
+            columns: Vec::new(),
+            date: None,
+            descending: false,
+            insert_date: None,
+            label: None,
+            model,
+            paging: Paging::default(),
+            sort_by: None,
+            xsi_type: None,
+        }
+    }
+
+    /// The model matched against.
+    pub fn model(&self) -> &M {
+        &self.model
+    }
+
+    /// The window of matching records selected.
+    pub fn paging(&self) -> &Paging {
+        &self.paging
+    }
+
+    /// Set the columns the host includes in each
+    /// record. Only applies to `ResultSet`
+    /// listings.
+    pub fn with_columns<S: AsRef<str>>(mut self, columns: &[S]) -> Self {
+        self.columns = columns.iter().map(|c| c.as_ref().to_owned()).collect();
+        self
+    }
+
+    /// Only match records dated within the range.
+    pub fn with_date<R: Into<DateRange>>(mut self, range: R) -> Self {
+        self.date = Some(range.into());
+        self
+    }
+
+    /// Sort records in descending rather than
+    /// ascending order.
+    pub fn with_descending(mut self, value: bool) -> Self {
+        self.descending = value;
+        self
+    }
+
+    /// Only match records inserted within the
+    /// range.
+    pub fn with_insert_date<R: Into<DateRange>>(mut self, range: R) -> Self {
+        self.insert_date = Some(range.into());
+        self
+    }
+
+    /// Only match records whose label matches the
+    /// pattern, where `*` matches any number of
+    /// characters.
+    pub fn with_label(mut self, pattern: &str) -> Self {
+        self.label = Some(pattern.to_owned());
+        self
+    }
+
+    /// Set the greatest number of records to
+    /// return.
+    pub fn with_limit(mut self, value: u64) -> Self {
+        self.paging.limit = Some(value);
+        self
+    }
+
+    /// Set the number of matching records to
+    /// skip.
+    pub fn with_offset(mut self, value: u64) -> Self {
+        self.paging.offset = Some(value);
+        self
+    }
+
+    /// Set the window of matching records
+    /// selected.
+    pub fn with_paging(mut self, paging: &Paging) -> Self {
+        self.paging.clone_from(paging);
+        self
+    }
+
+    /// Set the column records are sorted by.
+    pub fn with_sort_by(mut self, column: &str) -> Self {
+        self.sort_by = Some(column.to_owned());
+        self
+    }
+
+    /// Only match records of the XSI type.
+    pub fn with_xsi_type(mut self, xsi_type: &str) -> Self {
+        self.xsi_type = Some(xsi_type.to_owned());
+        self
+    }
+}
+
+impl<M> Query<M> {
+    /// Filter, sort and select the page from
+    /// records the host returned in full.
+    pub fn apply<R: Serialize>(&self, results: Vec<R>) -> Page<R> {
+        let mut results = self.filter(results, false);
+        if let Some(column) = &self.sort_by {
+            let mut keyed = results
+                .into_iter()
+                .map(|r| (fields_of(&r).get(column).cloned(), r))
+                .collect::<Vec<_>>();
+            keyed.sort_by(|(a, _), (b, _)| {
+                let ordering = compare_values(a.as_ref(), b.as_ref());
+                if self.descending { ordering.reverse() } else { ordering }
+            });
+            results = keyed.into_iter().map(|(_, r)| r).collect();
+        }
+        self.paging.apply(results)
+    }
+
+    /// Drop records from a page which do not
+    /// match the filters of this query. The
+    /// total the host reported no longer applies
+    /// if any are dropped.
+    pub fn filter_page<R: Serialize>(&self, mut page: Page<R>) -> Page<R> {
+        page.results = self.filter(page.results, true);
+        if (page.results.len() as u64) < page.scanned {
+            page.total_records = None;
+        }
+        page
+    }
+
+    /// Whether a record matches the filters of
+    /// this query. Records without a filtered
+    /// field do not match.
+    pub fn matches<R: Serialize>(&self, record: &R) -> bool {
+        self.check(record, false)
+    }
+
+    /// Whether a record matches the filters of
+    /// this query, leaving records without a
+    /// field to the host if it was sent that
+    /// filter.
+    fn check<R: Serialize>(&self, record: &R, sent: bool) -> bool {
+        if self.xsi_type.is_none()
+            && self.label.is_none()
+            && self.date.is_none()
+            && self.insert_date.is_none() {
+            return true
+        }
+
+        let fields = fields_of(record);
+        let text = |key: &str| fields.get(key).and_then(Value::as_str);
+        let date = |key: &str| text(key).and_then(parse_date);
+
+        // Records without a field cannot be checked
+        // against it, so only match if the host
+        // already filtered on it. Open date ranges
+        // are never sent.
+        let dated = |r: DateRange| sent && r.to_param().is_some();
+        self.xsi_type.as_ref().is_none_or(|x| text("xsiType").map_or(sent, |v| v.eq_ignore_ascii_case(x)))
+            && self.label.as_ref().is_none_or(|l| text("label").map_or(sent, |v| wildcard_match(l, v)))
+            && self.date.is_none_or(|r| date("date").map_or(dated(r), |d| r.contains(&d)))
+            && self.insert_date.is_none_or(|r| date("insert_date").map_or(dated(r), |d| r.contains(&d)))
+    }
+
+    fn filter<R: Serialize>(&self, results: Vec<R>, sent: bool) -> Vec<R> {
+        results.into_iter().filter(|r| self.check(r, sent)).collect()
+    }
+}
+
+/// Serializes the listing parameters of this
+/// query, but not its model.
+impl<M> Serialize for Query<M> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        if !self.columns.is_empty() {
+            map.serialize_entry("columns", &self.columns.join(","))?;
+        }
+        if let Some(xsi_type) = &self.xsi_type {
+            map.serialize_entry("xsiType", xsi_type)?;
+        }
+        if let Some(label) = &self.label {
+            map.serialize_entry("label", label)?;
+        }
+        if let Some(date) = self.date.and_then(DateRange::to_param) {
+            map.serialize_entry("date", &date)?;
+        }
+        if let Some(date) = self.insert_date.and_then(DateRange::to_param) {
+            map.serialize_entry("insert_date", &date)?;
+        }
+        if let Some(column) = &self.sort_by {
+            map.serialize_entry("sortBy", column)?;
+            map.serialize_entry("sortOrder", if self.descending { "DESC" } else { "ASC" })?;
+        }
+        if let Some(offset) = self.paging.offset {
+            map.serialize_entry("offset", &offset)?;
+        }
+        if let Some(limit) = self.paging.limit {
+            map.serialize_entry("limit", &limit)?;
+        }
+        map.end()
+    }
+}
+
+/// The fields of a record, keyed as they are
+/// named by the host.
+fn fields_of<R: Serialize>(record: &R) -> serde_json::Map<String, Value> {
+    match serde_json::to_value(record) {
+        Ok(Value::Object(fields)) => fields,
+        _ => serde_json::Map::new(),
+    }
+}
+
+/// Parse the date from the leading portion of a
+/// date or datetime given by the host.
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    let value = value.trim();
+    let date = value.get(..10).unwrap_or(value);
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(date, "%m/%d/%Y"))
+        .ok()
+}
+
+/// Compare values of a column, numerically when
+/// both are numbers. Missing values sort last.
+fn compare_values(a: Option<&Value>, b: Option<&Value>) -> Ordering {
+    let number = |v: &Value| v.as_f64().or_else(|| v.as_str()?.parse::<f64>().ok());
+    match (a, b) {
+        (Some(a), Some(b)) => match (number(a), number(b)) {
+            (Some(x), Some(y)) => x.total_cmp(&y),
+            _ => match (a.as_str(), b.as_str()) {
+                (Some(x), Some(y)) => x.cmp(y),
+                _ => a.to_string().cmp(&b.to_string()),
+            },
+        },
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Case-insensitive match of text against a
+/// pattern where `*` matches any number of
+/// characters.
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let text = text.to_lowercase();
+    let parts = pattern.split('*').collect::<Vec<_>>();
+    if parts.len() == 1 {
+        return pattern == text
+    }
+
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if text.len() < first.len() + last.len() || !text.starts_with(first) || !text.ends_with(last) {
+        return false
+    }
+    let mut rest = &text[first.len()..text.len() - last.len()];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    true
+}
@@ -1,46 +1,49 @@
 use async_trait::async_trait;
+use serde_json::Value;
 
 use crate::client::{Xnat, ClientCore, ClientREST};
 use crate::models::common::ModelField;
-use crate::uri::admin::PluginUri;
+use crate::uri::admin::{PluginUri, UsersUri};
 use crate::uri::data::{
     ExperimentUri,
     ProjectUriLegacy,
     SubjectUriLegacy,
 };
 use crate::models::{
-    Assessor, Experiment, FormatSpecifier, Plugin, Project, Resource, Scan, Subject
+    Assessor, Experiment, FormatSpecifier, Plugin, Project, Resource, Scan, Subject, User
 };
 use crate::version::Version;
-use super::crud::{CrudError, Retrieve};
-
-macro_rules! retrieve_rst_vec {
-    ($client:ident, $uri:ident, $model:ident) => {
-        $client
-            .get_any_result_from(&$uri, &$model)
-            .await?
-            .results()
-            .to_vec()
+use crate::Error;
+use super::crud::{try_retrieve, Retrieve};
+use super::paging::{Page, Rows};
+use super::query::Query;
+
+macro_rules! retrieve_rst_rows {
+    ($client:ident, $uri:ident, $model:ident, $query:ident) => {
+        Rows::Page($query.filter_page(Page::from_result_set(
+            $client.get_query_result_from::<_, _, Value>(&$uri, &$model, $query).await?,
+            $query.paging()
+        )))
     };
 }
 
-macro_rules! retrieve_its_vec {
-    ($client:ident, $uri:ident, $model:ident) => {
-        $client
-            .get_any_items_from(&$uri, &$model)
+macro_rules! retrieve_its_rows {
+    ($client:ident, $uri:ident, $model:ident, $query:ident) => {
+        Rows::All($client
+            .get_items_from::<_, Value>(&$uri, &$model)
             .await?
-            .iter()
-            .map(|i| i.unwrap())
-            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|i| i.data_fields)
+            .collect::<Vec<_>>())
     }
 }
 
-macro_rules! retrieve_vec {
-    ($client:ident, $uri:ident, $model:ident, $as_items:expr) => {
+macro_rules! retrieve_rows {
+    ($client:ident, $uri:ident, $model:ident, $query:ident, $as_items:expr) => {
         if $as_items {
-            retrieve_its_vec!($client, $uri, $model)
+            retrieve_its_rows!($client, $uri, $model, $query)
         } else {
-            retrieve_rst_vec!($client, $uri, $model)
+            retrieve_rst_rows!($client, $uri, $model, $query)
         }
     }
 }
@@ -60,13 +63,18 @@ macro_rules! set_resources {
     };
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 impl<V> Retrieve<Project> for Xnat<V>
 where
     Self: ClientCore<Version = V> + ClientREST,
     V: Version + ProjectUriLegacy,
 {
-    async fn get_any_from(&self, model: &Project) -> anyhow::Result<Vec<Project>> {
+    async fn get_any_from(&self, model: &Project) -> crate::Result<Vec<Project>> {
+        Ok(self.query(&Query::new(model.clone())).await?.into_results())
+    }
+
+    async fn fetch_rows(&self, query: &Query<Project>) -> crate::Result<Rows<Value>> {
+        let model = query.model();
         let mut uri = self.version().project_data();
         let mut model_clone = model.clone();
 
@@ -75,20 +83,25 @@ where
             Some(i) => {
                 uri = uri.with_id(i);
                 model_clone.id = None;
-                retrieve_its_vec!(self, uri, model_clone)
+                retrieve_its_rows!(self, uri, model_clone, query)
             },
-            None => retrieve_rst_vec!(self, uri, model_clone)
+            None => retrieve_rst_rows!(self, uri, model_clone, query)
         })
     }
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 impl<V> Retrieve<Subject> for Xnat<V>
 where
     Self: ClientCore<Version = V> + ClientREST,
     V: Version + SubjectUriLegacy,
 {
-    async fn get_any_from(&self, model: &Subject) -> anyhow::Result<Vec<Subject>> {
+    async fn get_any_from(&self, model: &Subject) -> crate::Result<Vec<Subject>> {
+        Ok(self.query(&Query::new(model.clone())).await?.into_results())
+    }
+
+    async fn fetch_rows(&self, query: &Query<Subject>) -> crate::Result<Rows<Value>> {
+        let model = query.model();
         let mut uri = self.version().subject_data();
         let mut model_clone = model.clone();
         // Ask the host to return a JSON response.
@@ -112,23 +125,28 @@ where
 
         let data = if let Subject { project: Some(p), .. } = &model_clone {
             let uri = uri.by_project(p);
-            retrieve_rst_vec!(self, uri, model_clone)
+            retrieve_rst_rows!(self, uri, model_clone, query)
         } else if get_as_item {
-            retrieve_its_vec!(self, uri, model_clone)
+            retrieve_its_rows!(self, uri, model_clone, query)
         } else {
-            retrieve_rst_vec!(self, uri, model_clone)
+            retrieve_rst_rows!(self, uri, model_clone, query)
         };
         Ok(data)
     }
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 impl<V> Retrieve<Experiment> for Xnat<V>
 where
     Self: ClientCore<Version = V> + ClientREST,
     V: Version + ProjectUriLegacy + SubjectUriLegacy + ExperimentUri,
 {
-    async fn get_any_from(&self, model: &Experiment) -> anyhow::Result<Vec<Experiment>> {
+    async fn get_any_from(&self, model: &Experiment) -> crate::Result<Vec<Experiment>> {
+        Ok(self.query(&Query::new(model.clone())).await?.into_results())
+    }
+
+    async fn fetch_rows(&self, query: &Query<Experiment>) -> crate::Result<Rows<Value>> {
+        let model = query.model();
         let mut model_clone = model.clone();
 
         // Filter over model values that are only
@@ -173,7 +191,7 @@ where
                         .with_subject(s)
                         .experiments()
                         .with_experiment(experiment);
-                    retrieve_its_vec!(self, uri, model_clone)
+                    retrieve_its_rows!(self, uri, model_clone, query)
                 },
                 [None, Some(p)] => {
                     let uri = self
@@ -182,7 +200,7 @@ where
                         .with_id(p)
                         .experiments()
                         .with_experiment(experiment);
-                    retrieve_its_vec!(self, uri, model_clone)
+                    retrieve_its_rows!(self, uri, model_clone, query)
                 },
                 [Some(s), None] => {
                     let uri = self
@@ -191,14 +209,14 @@ where
                         .with_subject(s)
                         .experiments()
                         .with_experiment(experiment);
-                    retrieve_its_vec!(self, uri, model_clone)
+                    retrieve_its_rows!(self, uri, model_clone, query)
                 },
                 [None, None] => {
                     let uri = self
                         .version()
                         .experiment_data()
                         .with_experiment(experiment);
-                    retrieve_its_vec!(self, uri, model_clone)
+                    retrieve_its_rows!(self, uri, model_clone, query)
                 },
             }
         } else {
@@ -211,7 +229,7 @@ where
                         .subjects()
                         .with_subject(s)
                         .experiments();
-                    retrieve_rst_vec!(self, uri, model_clone)
+                    retrieve_rst_rows!(self, uri, model_clone, query)
                 },
                 [None, Some(p)] => {
                     let uri = self
@@ -219,7 +237,7 @@ where
                         .project_data()
                         .with_id(p)
                         .experiments();
-                    retrieve_rst_vec!(self, uri, model_clone)
+                    retrieve_rst_rows!(self, uri, model_clone, query)
                 },
                 [Some(s), None] => {
                     let uri = self
@@ -227,13 +245,13 @@ where
                         .subject_data()
                         .with_subject(s)
                         .experiments();
-                    retrieve_rst_vec!(self, uri, model_clone)
+                    retrieve_rst_rows!(self, uri, model_clone, query)
                 },
                 [None, None] => {
                     let uri = self
                         .version()
                         .experiment_data();
-                    retrieve_rst_vec!(self, uri, model_clone)
+                    retrieve_rst_rows!(self, uri, model_clone, query)
                 },
             }
         };
@@ -241,13 +259,18 @@ where
     }
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 impl<V> Retrieve<Assessor> for Xnat<V>
 where
     Self: ClientCore<Version = V> + ClientREST,
     V: Version + ProjectUriLegacy + SubjectUriLegacy + ExperimentUri,
 {
-    async fn get_any_from(&self, model: &Assessor) -> anyhow::Result<Vec<Assessor>> {
+    async fn get_any_from(&self, model: &Assessor) -> crate::Result<Vec<Assessor>> {
+        Ok(self.query(&Query::new(model.clone())).await?.into_results())
+    }
+
+    async fn fetch_rows(&self, query: &Query<Assessor>) -> crate::Result<Rows<Value>> {
+        let model = query.model();
         let model_experiment = Experiment::from(model.clone());
         let mut model_clone = model.clone();
 
@@ -275,7 +298,7 @@ where
         let experiment = if let Some(e) = experiment {
             e
         } else {
-            return Err(CrudError::IdentifierRequired("experiment id or label".into()).into())
+            return Err(Error::IdentifierRequired("experiment id or label".into()))
         };
 
         // Clear out identifiers to avoid
@@ -300,7 +323,7 @@ where
                         .experiments()
                         .with_experiment(experiment);
                     let uri = uri.assessors().with_assessor(assessor);
-                    retrieve_its_vec!(self, uri, model_clone)
+                    retrieve_its_rows!(self, uri, model_clone, query)
                 },
                 [None, Some(p)] => {
                     let uri = self
@@ -310,7 +333,7 @@ where
                         .experiments()
                         .with_experiment(experiment);
                     let uri = uri.assessors().with_assessor(assessor);
-                    retrieve_its_vec!(self, uri, model_clone)
+                    retrieve_its_rows!(self, uri, model_clone, query)
                 },
                 [Some(s), None] => {
                     let uri = self
@@ -320,7 +343,7 @@ where
                         .experiments()
                         .with_experiment(experiment);
                     let uri = uri.assessors().with_assessor(assessor);
-                    retrieve_its_vec!(self, uri, model_clone)
+                    retrieve_its_rows!(self, uri, model_clone, query)
                 },
                 [None, None] => {
                     let uri = self
@@ -328,7 +351,7 @@ where
                         .experiment_data()
                         .with_experiment(experiment);
                     let uri = uri.assessors().with_assessor(assessor);
-                    retrieve_its_vec!(self, uri, model_clone)
+                    retrieve_its_rows!(self, uri, model_clone, query)
                 },
             }
         } else {
@@ -343,7 +366,7 @@ where
                         .experiments()
                         .with_experiment(experiment);
                     let uri = uri.assessors();
-                    retrieve_rst_vec!(self, uri, model_clone)
+                    retrieve_rst_rows!(self, uri, model_clone, query)
                 },
                 [None, Some(p)] => {
                     let uri = self
@@ -353,7 +376,7 @@ where
                         .experiments()
                         .with_experiment(experiment);
                     let uri = uri.assessors();
-                    retrieve_rst_vec!(self, uri, model_clone)
+                    retrieve_rst_rows!(self, uri, model_clone, query)
                 },
                 [Some(s), None] => {
                     let uri = self
@@ -363,7 +386,7 @@ where
                         .experiments()
                         .with_experiment(experiment);
                     let uri = uri.assessors();
-                    retrieve_rst_vec!(self, uri, model_clone)
+                    retrieve_rst_rows!(self, uri, model_clone, query)
                 },
                 [None, None] => {
                     let uri = self
@@ -371,7 +394,7 @@ where
                         .experiment_data()
                         .with_experiment(experiment);
                     let uri = uri.assessors();
-                    retrieve_rst_vec!(self, uri, model_clone)
+                    retrieve_rst_rows!(self, uri, model_clone, query)
                 },
             }
         };
@@ -379,33 +402,43 @@ where
     }
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 impl<V> Retrieve<Plugin> for Xnat<V>
 where
     Self: ClientCore<Version = V> + ClientREST,
     V: Version + PluginUri,
 {
-    async fn get_any_from(&self, model: &Plugin) -> anyhow::Result<Vec<Plugin>> {
+    async fn get_any_from(&self, model: &Plugin) -> crate::Result<Vec<Plugin>> {
+        Ok(self.query(&Query::new(model.clone())).await?.into_results())
+    }
+
+    async fn fetch_rows(&self, query: &Query<Plugin>) -> crate::Result<Rows<Value>> {
+        let model = query.model();
         let uri = self.version().plugins();
         let mut model_clone = model.clone();
         model_clone.name.take();
 
         Ok(if let Some(n) = &model.name {
             let uri = uri.with_plugin(n.property());
-            retrieve_its_vec!(self, uri, model_clone)
+            retrieve_its_rows!(self, uri, model_clone, query)
         } else {
-            retrieve_rst_vec!(self, uri, model_clone)
+            retrieve_rst_rows!(self, uri, model_clone, query)
         })
     }
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 impl<V> Retrieve<Resource> for Xnat<V>
 where
     Self: ClientCore<Version = V> + ClientREST,
     V: Version + ProjectUriLegacy + SubjectUriLegacy + ExperimentUri,
 {
-    async fn get_any_from(&self, model: &Resource) -> anyhow::Result<Vec<Resource>> {
+    async fn get_any_from(&self, model: &Resource) -> crate::Result<Vec<Resource>> {
+        Ok(self.query(&Query::new(model.clone())).await?.into_results())
+    }
+
+    async fn fetch_rows(&self, query: &Query<Resource>) -> crate::Result<Rows<Value>> {
+        let model = query.model();
         let mut model_clone = model.clone();
         model_clone.project.take();
         model_clone.subject.take();
@@ -439,7 +472,7 @@ where
                     .with_experiment(exp);
                 let uri = uri.scans().with_scan(scn);
                 let uri = set_resources!(uri, model);
-                retrieve_vec!(self, uri, model_clone, take_as_items)
+                retrieve_rows!(self, uri, model_clone, query, take_as_items)
             },
             Resource {
                 project: Some(pjt),
@@ -456,7 +489,7 @@ where
                     .experiments()
                     .with_experiment(exp);
                 let uri = set_resources!(uri, model);
-                retrieve_vec!(self, uri, model_clone, take_as_items)
+                retrieve_rows!(self, uri, model_clone, query, take_as_items)
             },
             Resource {
                 project: Some(pjt),
@@ -470,7 +503,7 @@ where
                     .subjects()
                     .with_subject(sbj);
                 let uri = set_resources!(uri, model);
-                retrieve_vec!(self, uri, model_clone, take_as_items)
+                retrieve_rows!(self, uri, model_clone, query, take_as_items)
             },
             Resource {
                 project: Some(pjt),
@@ -486,7 +519,7 @@ where
                     .with_experiment(exp);
                 let uri = uri.scans().with_scan(scn);
                 let uri = set_resources!(uri, model);
-                retrieve_vec!(self, uri, model_clone, take_as_items)
+                retrieve_rows!(self, uri, model_clone, query, take_as_items)
             },
             Resource { project: Some(pjt), experiment: Some(exp), .. } => {
                 let uri = self
@@ -496,42 +529,47 @@ where
                     .experiments()
                     .with_experiment(exp);
                 let uri = set_resources!(uri, model);
-                retrieve_vec!(self, uri, model_clone, take_as_items)
+                retrieve_rows!(self, uri, model_clone, query, take_as_items)
             },
             Resource { project: Some(pjt), .. } => {
                 let uri = self.version().project_data().with_id(pjt);
                 let uri = set_resources!(uri, model);
-                retrieve_vec!(self, uri, model_clone, take_as_items)
+                retrieve_rows!(self, uri, model_clone, query, take_as_items)
             },
             Resource { subject: Some(sbj), .. } => {
                 let uri = self.version().subject_data().with_subject(sbj);
                 let uri = set_resources!(uri, model);
-                retrieve_vec!(self, uri, model_clone, take_as_items)
+                retrieve_rows!(self, uri, model_clone, query, take_as_items)
             },
             Resource { experiment: Some(exp), scan: Some(scn), .. } => {
                 let uri = self.version().experiment_data().with_experiment(exp);
                 let uri = uri.scans().with_scan(scn);
                 let uri = set_resources!(uri, model);
-                retrieve_vec!(self, uri, model_clone, take_as_items)
+                retrieve_rows!(self, uri, model_clone, query, take_as_items)
             },
             Resource { experiment: Some(exp), .. } => {
                 let uri = self.version().experiment_data().with_experiment(exp);
                 let uri = set_resources!(uri, model);
-                retrieve_vec!(self, uri, model_clone, take_as_items)
+                retrieve_rows!(self, uri, model_clone, query, take_as_items)
             }
-            _ => return Err(CrudError::IdentifierRequired("any identifier".into()).into())
+            _ => return Err(Error::IdentifierRequired("any identifier".into()))
         };
         Ok(data)
     }
 }
 
-#[async_trait(?Send)]
+#[async_trait]
 impl<V> Retrieve<Scan> for Xnat<V>
 where
     Self: ClientCore<Version = V> + ClientREST,
     V: Version + ProjectUriLegacy + SubjectUriLegacy + ExperimentUri,
 {
-    async fn get_any_from(&self, model: &Scan) -> anyhow::Result<Vec<Scan>> {
+    async fn get_any_from(&self, model: &Scan) -> crate::Result<Vec<Scan>> {
+        Ok(self.query(&Query::new(model.clone())).await?.into_results())
+    }
+
+    async fn fetch_rows(&self, query: &Query<Scan>) -> crate::Result<Rows<Value>> {
+        let model = query.model();
         let mut model_clone = model.clone();
 
         // Filter over model values that are only
@@ -539,7 +577,7 @@ where
         let experiment = if let Some(e) = &model_clone.experiment {
             e
         } else {
-            return Err(CrudError::IdentifierRequired("experiment id or label".into()).into())
+            return Err(Error::IdentifierRequired("experiment id or label".into()))
         };
         let scan = &model_clone.id.clone();
         let subject = &model_clone.subject.clone();
@@ -567,7 +605,7 @@ where
                         .experiments()
                         .with_experiment(experiment);
                     let uri = uri.scans().with_scan(scan);
-                    retrieve_its_vec!(self, uri, model_clone)
+                    retrieve_its_rows!(self, uri, model_clone, query)
                 },
                 [None, Some(p)] => {
                     let uri = self
@@ -577,7 +615,7 @@ where
                         .experiments()
                         .with_experiment(experiment);
                     let uri = uri.scans().with_scan(scan);
-                    retrieve_its_vec!(self, uri, model_clone)
+                    retrieve_its_rows!(self, uri, model_clone, query)
                 },
                 [Some(s), None] => {
                     let uri = self
@@ -587,7 +625,7 @@ where
                         .experiments()
                         .with_experiment(experiment);
                     let uri = uri.scans().with_scan(scan);
-                    retrieve_its_vec!(self, uri, model_clone)
+                    retrieve_its_rows!(self, uri, model_clone, query)
                 },
                 [None, None] => {
                     let uri = self
@@ -595,7 +633,7 @@ where
                         .experiment_data()
                         .with_experiment(experiment);
                     let uri = uri.scans().with_scan(scan);
-                    retrieve_its_vec!(self, uri, model_clone)
+                    retrieve_its_rows!(self, uri, model_clone, query)
                 },
             }
         } else {
@@ -610,7 +648,7 @@ where
                         .experiments()
                         .with_experiment(experiment);
                     let uri = uri.scans();
-                    retrieve_rst_vec!(self, uri, model_clone)
+                    retrieve_rst_rows!(self, uri, model_clone, query)
                 },
                 [None, Some(p)] => {
                     let uri = self
@@ -620,7 +658,7 @@ where
                         .experiments()
                         .with_experiment(experiment);
                     let uri = uri.scans();
-                    retrieve_rst_vec!(self, uri, model_clone)
+                    retrieve_rst_rows!(self, uri, model_clone, query)
                 },
                 [Some(s), None] => {
                     let uri = self
@@ -630,7 +668,7 @@ where
                         .experiments()
                         .with_experiment(experiment);
                     let uri = uri.scans();
-                    retrieve_rst_vec!(self, uri, model_clone)
+                    retrieve_rst_rows!(self, uri, model_clone, query)
                 },
                 [None, None] => {
                     let uri = self
@@ -638,10 +676,35 @@ where
                         .experiment_data()
                         .with_experiment(experiment);
                     let uri = uri.scans();
-                    retrieve_rst_vec!(self, uri, model_clone)
+                    retrieve_rst_rows!(self, uri, model_clone, query)
                 },
             }
         };
         Ok(data)
     }
 }
+
+#[async_trait]
+impl<V> Retrieve<User> for Xnat<V>
+where
+    Self: ClientCore<Version = V> + ClientREST,
+    V: Version + UsersUri,
+{
+    async fn get_any_from(&self, model: &User) -> crate::Result<Vec<User>> {
+        Ok(self.query(&Query::new(model.clone())).await?.into_results())
+    }
+
+    async fn fetch_rows(&self, query: &Query<User>) -> crate::Result<Rows<Value>> {
+        let users = self.version().users();
+        let rows = if let Some(username) = &query.model().username {
+            let uri = users.with_username(username);
+            let response = try_retrieve(self.execute(self.get(&uri).await?).await?, |r| async { r }).await?;
+            vec![response.json::<Value>().await?]
+        } else {
+            let uri = users.profiles();
+            let response = try_retrieve(self.execute(self.get(&uri).await?).await?, |r| async { r }).await?;
+            response.json::<Vec<Value>>().await?
+        };
+        Ok(Rows::All(rows))
+    }
+}
@@ -0,0 +1,129 @@
+use std::fmt::{Debug, Display};
+
+use async_trait::async_trait;
+use serde::Serialize;
+
+use crate::client::{ClientCore, ClientREST, Xnat};
+use crate::models::{Experiment, ResultSet, SharedProject, Subject};
+use crate::uri::data::{ExperimentUri, ProjectUriLegacy, SubjectUriLegacy};
+use crate::uri::data::shared::SharedProjectUriBuilder;
+use crate::version::Version;
+use crate::{Error, UriBuilder};
+use super::crud::try_retrieve;
+
+/// Parameters sent when sharing an item into a
+/// project.
+#[derive(Serialize)]
+struct ShareParams<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<&'a str>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    primary: bool,
+}
+
+/// Type is able to share models into projects
+/// other than the one which owns them.
+#[async_trait]
+pub trait Share<M>
+where
+    M: Sync,
+{
+    /// Share a model into `project`, optionally
+    /// under a different label. A `primary` share
+    /// also hands ownership of the model to
+    /// `project`.
+    async fn share_into(&self, model: &M, project: &str, label: Option<&str>, primary: bool) -> crate::Result<()>;
+    /// Stop sharing a model into `project`.
+    async fn unshare_from(&self, model: &M, project: &str) -> crate::Result<()>;
+    /// List every project a model belongs to,
+    /// whether owned by or shared into.
+    async fn shared_projects(&self, model: &M) -> crate::Result<Vec<SharedProject>>;
+}
+
+/// Build the URI of the projects a subject
+/// belongs to, or of one of them.
+fn subject_uri<V>(version: &V, model: &Subject, shared: Option<&str>) -> crate::Result<String>
+where
+    V: ProjectUriLegacy + SubjectUriLegacy,
+{
+    let project = model
+        .project
+        .as_ref()
+        .ok_or_else(|| Error::IdentifierRequired("project id".into()))?;
+    let subject = model
+        .label
+        .as_ref()
+        .or(model.id.as_ref())
+        .ok_or_else(|| Error::IdentifierRequired("subject id or label".into()))?;
+
+    let subjects = version.project_data().with_id(project).subjects().with_subject(subject);
+    shared_uri(subjects.shared(), shared)
+}
+
+/// Build the URI of the projects an experiment
+/// belongs to, or of one of them.
+fn experiment_uri<V>(version: &V, model: &Experiment, shared: Option<&str>) -> crate::Result<String>
+where
+    V: ExperimentUri + ProjectUriLegacy + SubjectUriLegacy,
+{
+    let project = model.project.as_ref().or(model.subject_project.as_ref());
+    let subject = model.subject_label.as_ref().or(model.subject_id.as_ref());
+    let experiment = model.label.as_ref().or(model.id.as_ref());
+
+    match (project, subject, experiment, &model.id) {
+        (Some(p), Some(s), Some(e), _) => {
+            let subjects = version.project_data().with_id(p).subjects().with_subject(s);
+            let experiments = subjects.experiments().with_experiment(e);
+            shared_uri(experiments.shared(), shared)
+        },
+        (.., Some(id)) => {
+            let experiments = version.experiment_data().with_experiment(id);
+            shared_uri(experiments.shared(), shared)
+        },
+        _ => Err(Error::IdentifierRequired("experiment id".into())),
+    }
+}
+
+/// Build the URI of the projects an item belongs
+/// to, or of the one named `shared`.
+fn shared_uri<P>(uri: SharedProjectUriBuilder<'_, P>, shared: Option<&str>) -> crate::Result<String>
+where
+    P: Display + Debug,
+{
+    Ok(match shared {
+        Some(shared) => uri.with_shared(shared).build()?,
+        None => uri.build()?,
+    })
+}
+
+macro_rules! impl_share {
+    ($model:ty, $uri:ident) => {
+        #[async_trait]
+        impl<V> Share<$model> for Xnat<V>
+        where
+            Self: ClientCore<Version = V> + ClientREST,
+            V: Version + ExperimentUri + ProjectUriLegacy + SubjectUriLegacy + Send + Sync,
+        {
+            async fn share_into(&self, model: &$model, project: &str, label: Option<&str>, primary: bool) -> crate::Result<()> {
+                let uri = $uri(self.version(), model, Some(project))?;
+                let request = self.put(&uri).await?.query(&ShareParams { label, primary });
+                try_retrieve(self.execute(request).await?, |_| async {}).await
+            }
+
+            async fn unshare_from(&self, model: &$model, project: &str) -> crate::Result<()> {
+                let uri = $uri(self.version(), model, Some(project))?;
+                try_retrieve(self.execute(self.delete(&uri).await?).await?, |_| async {}).await
+            }
+
+            async fn shared_projects(&self, model: &$model) -> crate::Result<Vec<SharedProject>> {
+                let uri = $uri(self.version(), model, None)?;
+                let request = self.get(&uri).await?.query(&[("format", "json")]);
+                let response = try_retrieve(self.execute(request).await?, |r| async { r }).await?;
+                Ok(response.json::<ResultSet<SharedProject>>().await?.data.result)
+            }
+        }
+    };
+}
+
+impl_share!(Subject, subject_uri);
+impl_share!(Experiment, experiment_uri);
@@ -0,0 +1,223 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::client::{ClientCore, ClientREST, Xnat};
+use crate::uri::data::ArchiveUri;
+use crate::version::Version;
+use crate::Error;
+use super::crud::try_retrieve;
+use super::import::ImportLocation;
+
+/// Kind of a message XNAT reports while it
+/// processes an upload.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum StatusKind {
+    Completed,
+    Failed,
+    Processing,
+    Warning,
+    /// A kind this crate does not know of.
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
+/// A message XNAT reports while it processes an
+/// upload.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct StatusMessage {
+    pub status: StatusKind,
+    #[serde(rename = "msg", default)]
+    pub message: String,
+    /// Percentage of the upload processed, if
+    /// XNAT reported it.
+    #[serde(default)]
+    pub percent: Option<u8>,
+}
+
+/// Whether XNAT is still processing an upload.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UploadState {
+    Completed,
+    Failed,
+    InProgress,
+}
+
+/// Status of an upload, as of the last time it
+/// was checked.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UploadStatus {
+    pub state: UploadState,
+    /// Percentage of the upload processed so
+    /// far.
+    pub progress: u8,
+    /// Messages reported so far, oldest first.
+    pub messages: Vec<StatusMessage>,
+}
+
+impl UploadStatus {
+    /// Summarize the messages reported for an
+    /// upload.
+    pub fn from_messages(messages: Vec<StatusMessage>) -> Self {
+        let state = match messages.last().map(|m| &m.status) {
+            Some(StatusKind::Completed) => UploadState::Completed,
+            Some(StatusKind::Failed) => UploadState::Failed,
+            _ => UploadState::InProgress,
+        };
+        let progress = match state {
+            UploadState::Completed => 100,
+            _ => messages.iter().rev().find_map(|m| m.percent).unwrap_or_default().min(100),
+        };
+        Self { state, progress, messages }
+    }
+
+    /// Whether XNAT has finished with the upload,
+    /// successfully or not.
+    pub fn is_terminal(&self) -> bool {
+        self.state != UploadState::InProgress
+    }
+
+    /// Where the upload was placed, if XNAT
+    /// reported it on completion.
+    pub fn location(&self) -> Option<ImportLocation> {
+        self.messages
+            .iter()
+            .rev()
+            .filter(|m| m.status == StatusKind::Completed)
+            .find_map(|m| m.message.parse().ok())
+    }
+}
+
+/// Shapes XNAT responds to status requests with.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StatusBody {
+    Listing { msgs: Vec<Vec<StatusMessage>> },
+    Messages(Vec<StatusMessage>),
+}
+
+/// Create an identifier to start an upload or
+/// import with, so that it can be tracked.
+pub fn new_upload_id() -> String {
+    static COUNT: AtomicU64 = AtomicU64::new(0);
+    let millis = chrono::Utc::now().timestamp_millis();
+    format!("{millis}{:03}", COUNT.fetch_add(1, Ordering::Relaxed) % 1000)
+}
+
+/// Tracks an upload XNAT is processing.
+///
+/// ```no_compile
+/// let id = new_upload_id();
+/// let options = ImportOptions::default().with_upload_id(&id);
+/// client.import_archive(path, &options).await?;
+///
+/// let status = client
+///     .track_upload(&id)
+///     .wait_until_complete(Duration::from_secs(600))
+///     .await?;
+/// ```
+pub struct UploadHandle<'a, C: ?Sized> {
+    client:   &'a C,
+    id:       String,
+    interval: Duration,
+}
+
+impl<'a, C> UploadHandle<'a, C>
+where
+    C: TrackUpload + Sync + ?Sized,
+{
+    /// Track the upload started with `id`.
+    pub fn new(client: &'a C, id: &str) -> Self {
+        Self { client, id: id.to_owned(), interval: Duration::from_secs(1) }
+    }
+
+    /// Identifier of the upload.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Set how long to wait between checks of the
+    /// upload's status.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Check the status of the upload.
+    pub async fn status(&self) -> crate::Result<UploadStatus> {
+        self.client.upload_status(&self.id).await
+    }
+
+    /// Check the status of the upload until XNAT
+    /// has finished with it, successfully or
+    /// not. Fails with `Error::Timeout` if that
+    /// takes longer than `timeout`.
+    ///
+    /// Until XNAT registers the upload, its
+    /// status is not found; this is treated as
+    /// the upload being in progress.
+    pub async fn wait_until_complete(&self, timeout: Duration) -> crate::Result<UploadStatus> {
+        let poll = async {
+            loop {
+                match self.status().await {
+                    Ok(status) if status.is_terminal() => return Ok(status),
+                    Ok(_) | Err(Error::NotFound(_)) => tokio::time::sleep(self.interval).await,
+                    Err(e) => return Err(e),
+                }
+            }
+        };
+        tokio::time::timeout(timeout, poll)
+            .await
+            .map_err(|_| Error::Timeout(format!("upload `{}` after {timeout:?}", self.id)))?
+    }
+}
+
+impl<C: ?Sized> std::fmt::Debug for UploadHandle<'_, C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UploadHandle")
+            .field("id", &self.id)
+            .field("interval", &self.interval)
+            .finish()
+    }
+}
+
+/// Type is able to check the status of uploads
+/// XNAT is processing.
+#[async_trait]
+pub trait TrackUpload {
+    /// Check the status of the upload started
+    /// with `id`.
+    async fn upload_status(&self, id: &str) -> crate::Result<UploadStatus>;
+    /// Track the upload started with `id`.
+    fn track_upload(&self, id: &str) -> UploadHandle<'_, Self>
+    where
+        Self: Sync,
+    {
+        UploadHandle::new(self, id)
+    }
+}
+
+#[async_trait]
+impl<V> TrackUpload for Xnat<V>
+where
+    Self: ClientCore<Version = V> + ClientREST,
+    V: Version + ArchiveUri + Send + Sync,
+{
+    async fn upload_status(&self, id: &str) -> crate::Result<UploadStatus> {
+        let uri = self.version().archive_data().upload_status().with_upload_id(id);
+        let request = self.get(&uri).await?.query(&[("format", "json")]);
+        let response = try_retrieve(self.execute(request).await?, |r| async { r }).await?;
+
+        let url = response.url().to_owned();
+        let messages = match serde_json::from_slice(&response.bytes().await?) {
+            Ok(StatusBody::Listing { msgs }) => msgs.into_iter().flatten().collect(),
+            Ok(StatusBody::Messages(msgs)) => msgs,
+            Err(e) => return Err(Error::Deserialization { url: Some(url), source: e.into() }),
+        };
+        Ok(UploadStatus::from_messages(messages))
+    }
+}
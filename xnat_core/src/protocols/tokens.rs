@@ -0,0 +1,73 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::client::{ClientCore, ClientREST, Xnat};
+use crate::models::AliasToken;
+use crate::uri::admin::AuthUriLegacy;
+use crate::version::Version;
+use crate::UriBuilder;
+use super::crud::try_retrieve;
+
+/// Body XNAT responds with when asked to
+/// validate an alias token.
+#[derive(Deserialize)]
+struct Validation {
+    #[serde(default)]
+    valid: Option<String>,
+}
+
+/// Type is able to issue and revoke alias tokens,
+/// which stand in for the username and password
+/// of an account.
+#[async_trait]
+pub trait AliasTokens {
+    /// Issue a token for the account of this
+    /// client or, if a username is given, for
+    /// another account. Issuing tokens for other
+    /// accounts requires a site administrator.
+    async fn issue_alias_token(&self, username: Option<&str>) -> crate::Result<AliasToken>;
+    /// Check whether XNAT still accepts a token.
+    /// Returns the username of the account the
+    /// token stands in for, if it does.
+    async fn validate_alias_token(&self, token: &AliasToken) -> crate::Result<Option<String>>;
+    /// Revoke a token, so that it can no longer
+    /// be used to log in.
+    async fn invalidate_alias_token(&self, token: &AliasToken) -> crate::Result<()>;
+}
+
+#[async_trait]
+impl<V> AliasTokens for Xnat<V>
+where
+    Self: ClientCore<Version = V> + ClientREST,
+    V: Version + AuthUriLegacy + Send + Sync,
+{
+    async fn issue_alias_token(&self, username: Option<&str>) -> crate::Result<AliasToken> {
+        let auth = self.version().auth_legacy();
+        let services = auth.services();
+        let tokens = services.tokens();
+        let uri = match username {
+            Some(username) => tokens.issue().with_username(username).build()?,
+            None => tokens.issue().build()?,
+        };
+        let response = try_retrieve(self.execute(self.get(&uri).await?).await?, |r| async { r }).await?;
+        Ok(response.json().await?)
+    }
+
+    async fn validate_alias_token(&self, token: &AliasToken) -> crate::Result<Option<String>> {
+        let auth = self.version().auth_legacy();
+        let services = auth.services();
+        let tokens = services.tokens();
+        let uri = tokens.validate().with_token(&token.alias).with_secret(&token.secret);
+        let response = try_retrieve(self.execute(self.get(&uri).await?).await?, |r| async { r }).await?;
+        let validation = response.json::<Validation>().await?;
+        Ok(validation.valid.filter(|u| !u.is_empty()))
+    }
+
+    async fn invalidate_alias_token(&self, token: &AliasToken) -> crate::Result<()> {
+        let auth = self.version().auth_legacy();
+        let services = auth.services();
+        let tokens = services.tokens();
+        let uri = tokens.invalidate().with_token(&token.alias).with_secret(&token.secret);
+        try_retrieve(self.execute(self.get(&uri).await?).await?, |_| async {}).await
+    }
+}
@@ -0,0 +1,368 @@
+use async_trait::async_trait;
+use reqwest::StatusCode;
+
+use crate::client::{Xnat, ClientCore, ClientREST};
+use crate::models::{
+    Assessor, Experiment, FormatSpecifier, Project, Resource, Scan, Subject, User
+};
+use crate::uri::admin::UsersUri;
+use crate::uri::data::resources::ResourcesUriBuilder;
+use crate::uri::data::{
+    ExperimentUri,
+    ProjectUriLegacy,
+    SubjectUriLegacy,
+};
+use crate::version::Version;
+use crate::{Error, ErrorResponse, UriBuilder};
+use super::crud::{try_retrieve, Retrieve, Update};
+
+/// Takes the `Option` value for the specified
+/// attribute, returning a `Result`. Otherwise
+/// fails and returns a
+/// `Error::IdentifierRequired`.
+macro_rules! acquire_identifier {
+    ($attr:expr, $error:literal) => {
+        $attr.take().ok_or(Error::IdentifierRequired($error.to_string()))
+    };
+}
+
+/// Requests the model, as it is stored by the
+/// host, from the same URI it was written to.
+macro_rules! retrieve_stored {
+    ($client:ident, $uri:ident, $model:ident) => {
+        {
+            let mut query = $model::default();
+            query.format = Some(FormatSpecifier::Json);
+            $client
+                .get_any_items_from(&$uri, &query)
+                .await?
+                .iter()
+                .map(|i| i.unwrap())
+                .next()
+                .ok_or_else(|| Error::from_status(ErrorResponse::from_status(StatusCode::NOT_FOUND)))?
+        }
+    };
+}
+
+#[async_trait]
+impl<V> Update<Project> for Xnat<V>
+where
+    Self: ClientCore<Version = V> + ClientREST + Retrieve<Project>,
+    V: Version + ProjectUriLegacy,
+{
+    async fn update_once(&self, model: Project) -> crate::Result<Project> {
+        let mut model_clone = model.clone();
+        let project = acquire_identifier!(model_clone.id, "project id")?;
+
+        let uri = self.version().project_data().with_id(project);
+        try_retrieve(
+            self.execute(self.put(&uri).await?.json(&model_clone)).await?,
+            |r| async { r }
+        ).await?;
+        Ok(retrieve_stored!(self, uri, Project))
+    }
+}
+
+#[async_trait]
+impl<V> Update<Subject> for Xnat<V>
+where
+    Self: ClientCore<Version = V> + ClientREST + Retrieve<Subject>,
+    V: Version + ProjectUriLegacy + SubjectUriLegacy,
+{
+    async fn update_once(&self, model: Subject) -> crate::Result<Subject> {
+        let mut model_clone = model.clone();
+        let project = acquire_identifier!(model_clone.project, "project id")?;
+        let subject = acquire_identifier!(
+            model_clone
+                .id
+                .as_ref()
+                .or(model_clone.label.as_ref()),
+            "subject id")?
+            .to_owned();
+        model_clone.id.take();
+
+        let uri = self
+            .version()
+            .project_data()
+            .with_id(project)
+            .subjects()
+            .with_subject(subject);
+        try_retrieve(
+            self.execute(self.put(&uri).await?.json(&model_clone)).await?,
+            |r| async { r }
+        ).await?;
+        Ok(retrieve_stored!(self, uri, Subject))
+    }
+}
+
+#[async_trait]
+impl<V> Update<Experiment> for Xnat<V>
+where
+    Self: ClientCore<Version = V> + ClientREST + Retrieve<Experiment>,
+    V: Version + ExperimentUri + ProjectUriLegacy + SubjectUriLegacy,
+{
+    async fn update_once(&self, model: Experiment) -> crate::Result<Experiment> {
+        let mut model_clone = model.clone();
+        let project = acquire_identifier!(
+            model_clone
+                .project
+                .as_ref()
+                .or(model_clone.subject_project.as_ref()),
+            "project id")?
+            .to_owned();
+        let subject = acquire_identifier!(
+            model_clone
+                .subject_id
+                .as_ref()
+                .or(model_clone.subject_label.as_ref()),
+            "subject id")?
+            .to_owned();
+        let session = acquire_identifier!(
+            model_clone
+                .id
+                .as_ref()
+                .or(model_clone.label.as_ref()),
+            "experiment id")?
+            .to_owned();
+        model_clone.project.take();
+        model_clone.subject_project.take();
+        model_clone.subject_id.take();
+        model_clone.subject_label.take();
+        model_clone.id.take();
+
+        let uri = self
+            .version()
+            .project_data()
+            .with_id(project)
+            .subjects()
+            .with_subject(subject)
+            .experiments()
+            .with_experiment(session);
+        try_retrieve(
+            self.execute(self.put(&uri).await?.json(&model_clone)).await?,
+            |r| async { r }
+        ).await?;
+        Ok(retrieve_stored!(self, uri, Experiment))
+    }
+}
+
+#[async_trait]
+impl<V> Update<Scan> for Xnat<V>
+where
+    Self: ClientCore<Version = V> + ClientREST + Retrieve<Scan>,
+    V: Version + ExperimentUri + ProjectUriLegacy + SubjectUriLegacy,
+{
+    async fn update_once(&self, model: Scan) -> crate::Result<Scan> {
+        let mut model_clone = model.clone();
+        let project = acquire_identifier!(model_clone.project, "project id")?;
+        let subject = acquire_identifier!(model_clone.subject, "subject id")?;
+        let session = acquire_identifier!(model_clone.experiment, "experiment id")?;
+        let scan = acquire_identifier!(model_clone.id, "scan id")?;
+
+        let uri = self
+            .version()
+            .project_data()
+            .with_id(project)
+            .subjects()
+            .with_subject(subject)
+            .experiments()
+            .with_experiment(session);
+        let uri = uri.scans().with_scan(scan);
+        try_retrieve(
+            self.execute(self.put(&uri).await?.json(&model_clone)).await?,
+            |r| async { r }
+        ).await?;
+
+        // Runtime identifiers are not returned by
+        // the host, so they are carried over.
+        let mut stored = retrieve_stored!(self, uri, Scan);
+        stored.project.clone_from(&model.project);
+        stored.subject.clone_from(&model.subject);
+        stored.experiment.clone_from(&model.experiment);
+        Ok(stored)
+    }
+}
+
+#[async_trait]
+impl<V> Update<Assessor> for Xnat<V>
+where
+    Self: ClientCore<Version = V> + ClientREST + Retrieve<Assessor>,
+    V: Version + ExperimentUri + ProjectUriLegacy + SubjectUriLegacy,
+{
+    async fn update_once(&self, model: Assessor) -> crate::Result<Assessor> {
+        let mut model_clone = model.clone();
+        let project = acquire_identifier!(model_clone.project, "project id")?;
+        let subject = acquire_identifier!(model_clone.subject, "subject id")?;
+        let session = acquire_identifier!(
+            model_clone
+                .session_id
+                .as_ref()
+                .or(model_clone.session_label.as_ref()),
+            "experiment id")?
+            .to_owned();
+        let assessor = acquire_identifier!(
+            model_clone
+                .id
+                .as_ref()
+                .or(model_clone.label.as_ref()),
+            "assessor id")?
+            .to_owned();
+        model_clone.session_id.take();
+        model_clone.session_label.take();
+        model_clone.id.take();
+
+        let uri = self
+            .version()
+            .project_data()
+            .with_id(project)
+            .subjects()
+            .with_subject(subject)
+            .experiments()
+            .with_experiment(session);
+        let uri = uri.assessors().with_assessor(assessor);
+        try_retrieve(
+            self.execute(self.put(&uri).await?.json(&model_clone)).await?,
+            |r| async { r }
+        ).await?;
+
+        let mut stored = retrieve_stored!(self, uri, Assessor);
+        stored.subject.clone_from(&model.subject);
+        Ok(stored)
+    }
+}
+
+#[async_trait]
+impl<V> Update<Resource> for Xnat<V>
+where
+    Self: ClientCore<Version = V> + ClientREST + Retrieve<Resource>,
+    V: Version + ExperimentUri + ProjectUriLegacy + SubjectUriLegacy,
+{
+    async fn update_once(&self, model: Resource) -> crate::Result<Resource> {
+        let mut model_clone = model.clone();
+        model_clone.project.take();
+        model_clone.subject.take();
+        model_clone.experiment.take();
+        model_clone.scan.take();
+        model_clone.id.take();
+        model_clone.collection.take();
+        model_clone.name.take();
+
+        let uri = match &model {
+            Resource {
+                project: Some(pjt),
+                subject: Some(sbj),
+                experiment: Some(exp),
+                scan: Some(scn),
+                ..
+            } => {
+                self.version()
+                    .project_data()
+                    .with_id(pjt)
+                    .subjects()
+                    .with_subject(sbj)
+                    .experiments()
+                    .with_experiment(exp)
+                    .scans()
+                    .with_scan(scn)
+                    .build()
+            },
+            Resource {
+                project: Some(pjt),
+                subject: Some(sbj),
+                experiment: Some(exp),
+                ..
+            } => {
+                self.version()
+                    .project_data()
+                    .with_id(pjt)
+                    .subjects()
+                    .with_subject(sbj)
+                    .experiments()
+                    .with_experiment(exp)
+                    .build()
+            },
+            Resource {
+                project: Some(pjt),
+                subject: Some(sbj),
+                ..
+            } => {
+                self.version()
+                    .project_data()
+                    .with_id(pjt)
+                    .subjects()
+                    .with_subject(sbj)
+                    .build()
+            },
+            Resource {
+                project: Some(pjt),
+                ..
+            } => self.version().project_data().with_id(pjt).build(),
+            _ => return Err(Error::IdentifierRequired("any identifiers".into()))
+        }?;
+        let collection = acquire_identifier!(
+            model.collection.as_ref(),
+            "resource collection")?;
+
+        let resources = ResourcesUriBuilder::default().with_parent(&uri);
+        let resource = resources.with_resource(collection);
+        let write_uri = match &model.name {
+            Some(n) => resource.clone().with_file(n),
+            None    => resource.clone(),
+        };
+        try_retrieve(
+            self.execute(self.put(&write_uri).await?.json(&model_clone)).await?,
+            |r| async { r }
+        ).await?;
+
+        // Resources and files are not available as
+        // items, so the stored model must be picked
+        // out of its parent listing instead.
+        let query = Resource { format: Some("json".into()), ..Default::default() };
+        let mut stored = match &model.name {
+            Some(n) => {
+                let listing = resource.build_join("files")?;
+                self.get_any_result_from(&listing, &query)
+                    .await?
+                    .results()
+                    .iter()
+                    .find(|r| r.name.as_ref() == Some(n))
+                    .cloned()
+            },
+            None => {
+                let listing = ResourcesUriBuilder::default().with_parent(&uri);
+                self.get_any_result_from(&listing, &query)
+                    .await?
+                    .results()
+                    .iter()
+                    .find(|r| r.label.as_ref() == Some(collection))
+                    .cloned()
+            }
+        }
+        .ok_or_else(|| Error::from_status(ErrorResponse::from_status(StatusCode::NOT_FOUND)))?;
+        stored.project.clone_from(&model.project);
+        stored.subject.clone_from(&model.subject);
+        stored.experiment.clone_from(&model.experiment);
+        stored.scan.clone_from(&model.scan);
+        stored.collection.clone_from(&model.collection);
+        Ok(stored)
+    }
+}
+
+#[async_trait]
+impl<V> Update<User> for Xnat<V>
+where
+    Self: ClientCore<Version = V> + ClientREST + Retrieve<User>,
+    V: Version + UsersUri,
+{
+    async fn update_once(&self, model: User) -> crate::Result<User> {
+        let mut model_clone = model.clone();
+        let username = acquire_identifier!(model_clone.username, "username")?;
+
+        let uri = self.version().users().with_username(&username);
+        try_retrieve(
+            self.execute(self.put(&uri).await?.json(&model_clone)).await?,
+            |r| async { r }
+        ).await?;
+        self.get_one_from(&User::new(&username)).await
+    }
+}
@@ -0,0 +1,318 @@
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::task::{Context, Poll};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_core::Stream;
+use reqwest::{header::CONTENT_LENGTH, multipart::{Form, Part}, Body};
+use serde::Serialize;
+use tokio::io::AsyncRead;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio_util::io::ReaderStream;
+
+use crate::client::{ClientREST, Xnat};
+use crate::version::Version;
+use crate::{Error, UriBuilder};
+use super::crud::try_retrieve;
+
+/// Progress of an upload, reported each time a
+/// chunk of the body is sent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UploadProgress {
+    /// Name of the file the last chunk was read
+    /// from.
+    pub file: String,
+    /// Number of bytes sent so far, across all
+    /// files of the upload.
+    pub bytes_sent: u64,
+    /// Number of bytes in the upload, if known.
+    pub total_bytes: Option<u64>,
+}
+
+/// Function called with the progress of an
+/// upload.
+type ProgressFn = Arc<dyn Fn(UploadProgress) + Send + Sync>;
+
+/// Options sent along with files uploaded into a
+/// resource.
+///
+/// ```no_compile
+/// let options = UploadOptions::default()
+///     .with_format("NIFTI")
+///     .with_content("T1_DERIVED")
+///     .with_overwrite(true)
+///     .with_progress(|p| println!("{}/{:?}", p.bytes_sent, p.total_bytes));
+/// ```
+#[derive(Clone, Default, Serialize)]
+pub struct UploadOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    extract: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    overwrite: Option<bool>,
+    #[serde(skip)]
+    progress: Option<ProgressFn>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tags: Option<String>,
+    #[serde(rename = "http-session-listener", skip_serializing_if = "Option::is_none")]
+    upload_id: Option<String>,
+}
+
+impl UploadOptions {
+    /// Set the content label of the uploaded
+    /// files, such as `T1_RAW`.
+    pub fn with_content(mut self, content: &str) -> Self {
+        self.content = Some(content.to_owned());
+        self
+    }
+
+    /// Whether the host unpacks uploaded archives
+    /// into the resource.
+    pub fn with_extract(mut self, value: bool) -> Self {
+        self.extract = Some(value);
+        self
+    }
+
+    /// Set the format of the uploaded files, such
+    /// as `DICOM` or `NIFTI`.
+    pub fn with_format(mut self, format: &str) -> Self {
+        self.format = Some(format.to_owned());
+        self
+    }
+
+    /// Whether files already in the resource are
+    /// replaced.
+    pub fn with_overwrite(mut self, value: bool) -> Self {
+        self.overwrite = Some(value);
+        self
+    }
+
+    /// Call `callback` with the progress of the
+    /// upload each time a chunk is sent.
+    pub fn with_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(UploadProgress) + Send + Sync + 'static,
+    {
+        self.progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// Send the progress of the upload to a
+    /// channel each time a chunk is sent.
+    pub fn with_progress_channel(self, sender: UnboundedSender<UploadProgress>) -> Self {
+        self.with_progress(move |p| { let _ = sender.send(p); })
+    }
+
+    /// Set the tags of the uploaded files.
+    pub fn with_tags<S: AsRef<str>>(mut self, tags: &[S]) -> Self {
+        let tags = tags.iter().map(|t| t.as_ref()).collect::<Vec<_>>();
+        self.tags = Some(tags.join(","));
+        self
+    }
+
+    /// Set the identifier the upload's status is
+    /// tracked by. See `new_upload_id`.
+    pub fn with_upload_id(mut self, id: &str) -> Self {
+        self.upload_id = Some(id.to_owned());
+        self
+    }
+}
+
+impl Debug for UploadOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UploadOptions")
+            .field("content", &self.content)
+            .field("extract", &self.extract)
+            .field("format", &self.format)
+            .field("overwrite", &self.overwrite)
+            .field("progress", &self.progress.is_some())
+            .field("tags", &self.tags)
+            .field("upload_id", &self.upload_id)
+            .finish()
+    }
+}
+
+/// Query parameter telling the host the file is
+/// the body of the request itself.
+#[derive(Serialize)]
+pub(super) struct InBody {
+    pub inbody: bool,
+}
+
+/// Counts the bytes sent across every file of an
+/// upload.
+#[derive(Clone)]
+struct Tracker {
+    callback:    Option<ProgressFn>,
+    sent:        Arc<AtomicU64>,
+    total_bytes: Option<u64>,
+}
+
+impl Tracker {
+    fn new(options: &UploadOptions, total_bytes: Option<u64>) -> Self {
+        Self {
+            callback: options.progress.clone(),
+            sent: Arc::new(AtomicU64::new(0)),
+            total_bytes,
+        }
+    }
+
+    /// Stream the contents of `reader` as a
+    /// request body, reporting progress as
+    /// `file`.
+    fn body<R>(&self, file: &str, reader: R) -> Body
+    where
+        R: AsyncRead + Send + Sync + Unpin + 'static,
+    {
+        Body::wrap_stream(ProgressStream {
+            file: file.to_owned(),
+            inner: ReaderStream::new(reader),
+            tracker: self.clone(),
+        })
+    }
+}
+
+/// Stream of chunks read for a request body,
+/// reporting each chunk sent.
+struct ProgressStream<R> {
+    file:    String,
+    inner:   ReaderStream<R>,
+    tracker: Tracker,
+}
+
+impl<R: AsyncRead + Unpin> Stream for ProgressStream<R> {
+    type Item = std::io::Result<Bytes>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let polled = Pin::new(&mut this.inner).poll_next(cx);
+        if let Poll::Ready(Some(Ok(chunk))) = &polled {
+            let tracker = &this.tracker;
+            let sent = tracker.sent.fetch_add(chunk.len() as u64, Ordering::Relaxed) + chunk.len() as u64;
+            if let Some(callback) = &tracker.callback {
+                callback(UploadProgress {
+                    file: this.file.clone(),
+                    bytes_sent: sent,
+                    total_bytes: tracker.total_bytes,
+                });
+            }
+        }
+        polled
+    }
+}
+
+/// The name a local file is uploaded as.
+pub(super) fn file_name(path: &Path) -> crate::Result<String> {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .map(str::to_owned)
+        .ok_or_else(|| Error::Config(format!("`{}` is not a file", path.display())))
+}
+
+/// Type is able to upload files into a resource.
+///
+/// Bodies are streamed from their source rather
+/// than read into memory, so uploads are never
+/// retried.
+#[async_trait]
+pub trait Upload {
+    /// Upload a local file into a resource. `uri`
+    /// must name the file within the resource,
+    /// `{parent}/resources/{resource}/files/{file}`.
+    async fn upload_file<UB>(&self, uri: &UB, path: &Path, options: &UploadOptions) -> crate::Result<()>
+    where
+        UB: UriBuilder + Sync,
+    {
+        let file = tokio::fs::File::open(path).await?;
+        let length = file.metadata().await?.len();
+        self.upload_reader(uri, file, Some(length), options).await
+    }
+    /// Upload the contents of a reader into a
+    /// resource. `uri` must name the file within
+    /// the resource,
+    /// `{parent}/resources/{resource}/files/{file}`.
+    async fn upload_reader<UB, R>(
+        &self,
+        uri: &UB,
+        reader: R,
+        length: Option<u64>,
+        options: &UploadOptions,
+    ) -> crate::Result<()>
+    where
+        UB: UriBuilder + Sync,
+        R: AsyncRead + Send + Sync + Unpin + 'static;
+    /// Upload many local files into a resource in
+    /// a single multipart request. `uri` must name
+    /// the resource itself,
+    /// `{parent}/resources/{resource}`.
+    async fn upload_files<UB>(&self, uri: &UB, paths: &[PathBuf], options: &UploadOptions) -> crate::Result<()>
+    where
+        UB: UriBuilder + Sync;
+}
+
+#[async_trait]
+impl<V> Upload for Xnat<V>
+where
+    Self: ClientREST,
+    V: Version + Send + Sync,
+{
+    async fn upload_reader<UB, R>(
+        &self,
+        uri: &UB,
+        reader: R,
+        length: Option<u64>,
+        options: &UploadOptions,
+    ) -> crate::Result<()>
+    where
+        UB: UriBuilder + Sync,
+        R: AsyncRead + Send + Sync + Unpin + 'static,
+    {
+        let uri = uri.build()?;
+        let name = uri.rsplit('/').next().unwrap_or_default().to_owned();
+        let tracker = Tracker::new(options, length);
+        let mut request = self
+            .put(&uri)
+            .await?
+            .query(options)
+            .query(&InBody { inbody: true })
+            .body(tracker.body(&name, reader));
+        if let Some(length) = length {
+            request = request.header(CONTENT_LENGTH, length);
+        }
+        try_retrieve(self.execute(request).await?, |_| async {}).await
+    }
+
+    async fn upload_files<UB>(&self, uri: &UB, paths: &[PathBuf], options: &UploadOptions) -> crate::Result<()>
+    where
+        UB: UriBuilder + Sync,
+    {
+        let mut files = Vec::with_capacity(paths.len());
+        for path in paths {
+            let file = tokio::fs::File::open(path).await?;
+            let length = file.metadata().await?.len();
+            files.push((file_name(path)?, file, length));
+        }
+
+        let total_bytes = files.iter().map(|(_, _, l)| l).sum();
+        let tracker = Tracker::new(options, Some(total_bytes));
+        let form = files.into_iter().fold(Form::new(), |form, (name, file, length)| {
+            let part = Part::stream_with_length(tracker.body(&name, file), length).file_name(name);
+            form.part("file", part)
+        });
+
+        let uri = format!("{}/files", uri.build()?);
+        let request = self
+            .post(&uri)
+            .await?
+            .query(options)
+            .multipart(form);
+        try_retrieve(self.execute(request).await?, |_| async {}).await
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::client::{ClientCore, ClientREST, Xnat};
+use crate::uri::admin::UsersUri;
+use crate::version::Version;
+use super::crud::try_retrieve;
+
+/// Sessions XNAT reports for an active user.
+#[derive(Deserialize)]
+struct ActiveUser {
+    #[serde(default)]
+    sessions: Vec<String>,
+}
+
+/// Type is able to administer the accounts of an
+/// XNAT site.
+#[async_trait]
+pub trait UserAdmin {
+    /// Enable or disable an account. A disabled
+    /// account cannot log in.
+    async fn set_enabled(&self, username: &str, enabled: bool) -> crate::Result<()>;
+    /// Mark the email of an account as verified,
+    /// or not.
+    async fn set_verified(&self, username: &str, verified: bool) -> crate::Result<()>;
+    /// List the site roles of an account.
+    async fn user_roles(&self, username: &str) -> crate::Result<Vec<String>>;
+    /// Assign a site role, such as
+    /// `Administrator`, to an account.
+    async fn add_role(&self, username: &str, role: &str) -> crate::Result<()>;
+    /// Take a site role away from an account.
+    async fn remove_role(&self, username: &str, role: &str) -> crate::Result<()>;
+    /// List the groups an account belongs to.
+    async fn user_groups(&self, username: &str) -> crate::Result<Vec<String>>;
+    /// Add an account to a group, by the group's
+    /// id such as `{project}_owner`.
+    async fn add_group(&self, username: &str, group: &str) -> crate::Result<()>;
+    /// Remove an account from a group.
+    async fn remove_group(&self, username: &str, group: &str) -> crate::Result<()>;
+    /// List the ids of the sessions an account
+    /// has open.
+    async fn active_sessions(&self, username: &str) -> crate::Result<Vec<String>>;
+    /// List the accounts with sessions open, and
+    /// the ids of those sessions.
+    async fn active_users(&self) -> crate::Result<HashMap<String, Vec<String>>>;
+}
+
+#[async_trait]
+impl<V> UserAdmin for Xnat<V>
+where
+    Self: ClientCore<Version = V> + ClientREST,
+    V: Version + UsersUri + Send + Sync,
+{
+    async fn set_enabled(&self, username: &str, enabled: bool) -> crate::Result<()> {
+        let users = self.version().users().with_username(username);
+        let uri = users.enabled().with_flag(enabled);
+        try_retrieve(self.execute(self.put(&uri).await?).await?, |_| async {}).await
+    }
+
+    async fn set_verified(&self, username: &str, verified: bool) -> crate::Result<()> {
+        let users = self.version().users().with_username(username);
+        let uri = users.verified().with_flag(verified);
+        try_retrieve(self.execute(self.put(&uri).await?).await?, |_| async {}).await
+    }
+
+    async fn user_roles(&self, username: &str) -> crate::Result<Vec<String>> {
+        let users = self.version().users().with_username(username);
+        let response = try_retrieve(self.execute(self.get(&users.roles()).await?).await?, |r| async { r }).await?;
+        Ok(response.json().await?)
+    }
+
+    async fn add_role(&self, username: &str, role: &str) -> crate::Result<()> {
+        let users = self.version().users().with_username(username);
+        let uri = users.roles().with_role(role);
+        try_retrieve(self.execute(self.put(&uri).await?).await?, |_| async {}).await
+    }
+
+    async fn remove_role(&self, username: &str, role: &str) -> crate::Result<()> {
+        let users = self.version().users().with_username(username);
+        let uri = users.roles().with_role(role);
+        try_retrieve(self.execute(self.delete(&uri).await?).await?, |_| async {}).await
+    }
+
+    async fn user_groups(&self, username: &str) -> crate::Result<Vec<String>> {
+        let users = self.version().users().with_username(username);
+        let response = try_retrieve(self.execute(self.get(&users.groups()).await?).await?, |r| async { r }).await?;
+        Ok(response.json().await?)
+    }
+
+    async fn add_group(&self, username: &str, group: &str) -> crate::Result<()> {
+        let users = self.version().users().with_username(username);
+        let uri = users.groups().with_group(group);
+        try_retrieve(self.execute(self.put(&uri).await?).await?, |_| async {}).await
+    }
+
+    async fn remove_group(&self, username: &str, group: &str) -> crate::Result<()> {
+        let users = self.version().users().with_username(username);
+        let uri = users.groups().with_group(group);
+        try_retrieve(self.execute(self.delete(&uri).await?).await?, |_| async {}).await
+    }
+
+    async fn active_sessions(&self, username: &str) -> crate::Result<Vec<String>> {
+        let users = self.version().users();
+        let uri = users.active().with_username(username);
+        let response = try_retrieve(self.execute(self.get(&uri).await?).await?, |r| async { r }).await?;
+        Ok(response.json().await?)
+    }
+
+    async fn active_users(&self) -> crate::Result<HashMap<String, Vec<String>>> {
+        let users = self.version().users();
+        let response = try_retrieve(self.execute(self.get(&users.active()).await?).await?, |r| async { r }).await?;
+        let active = response
+            .json::<HashMap<String, ActiveUser>>()
+            .await?
+            .into_iter()
+            .map(|(username, active)| (username, active.sessions))
+            .collect();
+        Ok(active)
+    }
+}
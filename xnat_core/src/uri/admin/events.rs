@@ -155,7 +155,7 @@ impl EventUriBuilder<'_> {
         if is_event_type!(One)(self) {
             self.build_join("properties")
         } else {
-            Err(UriBuildError::Validation.into())
+            Err(UriBuildError::Validation)
         }
     }
 }
@@ -117,7 +117,7 @@ impl DisplaysUriBuilder<'_> {
         if self.display.is_none() {
             self.build_join("modified")
         } else {
-            Err(UriBuildError::Validation.into())
+            Err(UriBuildError::Validation)
         }
     }
 }
@@ -279,7 +279,7 @@ impl UserUriBuilder<String>
         if self.username.is_none() {
             self.build_join("projects")
         } else {
-            Err(UriBuildError::Validation.into())
+            Err(UriBuildError::Validation)
         }
     }
 
@@ -288,7 +288,7 @@ impl UserUriBuilder<String>
         if self.username.is_none() {
             self.build_join("username")
         } else {
-            Err(UriBuildError::Validation.into())
+            Err(UriBuildError::Validation)
         }
     }
 
@@ -369,7 +369,7 @@ impl ResourcesUriBuilder<'_> {
         if self.file.is_none() && self.folder.is_some() {
             self.build_join("projects")
         } else {
-            Err(UriBuildError::Validation.into())
+            Err(UriBuildError::Validation)
         }
     }
 }
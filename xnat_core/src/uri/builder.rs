@@ -2,7 +2,7 @@ use std::fmt::Display;
 
 use thiserror::Error;
 
-pub type BuildResult = anyhow::Result<String>;
+pub type BuildResult = Result<String, UriBuildError>;
 
 #[derive(Debug, Error)]
 pub enum UriBuildError {
@@ -31,7 +31,7 @@ pub trait UriBuilder: Display {
     fn build_join_if<UB: UriBuilder>(&self, other: UB, predicate: fn(&Self) -> bool) -> BuildResult {
         predicate(self)
             .then(|| self.build_join(other))
-            .or_else(|| Err(UriBuildError::UnrecognizedPattern.into()).into())
+            .or_else(|| Err(UriBuildError::UnrecognizedPattern).into())
             .unwrap()
     }
 }
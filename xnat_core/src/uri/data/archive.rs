@@ -102,7 +102,7 @@ where
     #[param]
     scan: Option<String>,
     #[param]
-    timestamp: Option<u64>,
+    timestamp: Option<String>,
     #[parent]
     parent: Option<&'a Parent>
 }
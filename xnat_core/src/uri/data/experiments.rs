@@ -71,6 +71,20 @@ impl<Parent> ExperimentScanUriBuilder<'_, Parent>
 where
     Parent: ExperimentDataUriBuilder + Default,
 {
+    /// Select many scans at once, as XNAT
+    /// accepts for bulk downloads. `ALL` selects
+    /// every scan of the experiment.
+    pub fn with_scans<S: AsRef<str>>(self, scans: &[S]) -> Self {
+        let scans = scans.iter().map(|s| s.as_ref()).collect::<Vec<_>>();
+        self.with_scan(scans.join(","))
+    }
+
+    /// Produce the URI path listing the files of
+    /// the selected scans.
+    pub fn files(&self) -> crate::BuildResult {
+        self.build_join_if("files", |this| this.scan.is_some())
+    }
+
     /// Continue the builder into a
     /// `ResourceUriBuilder`.
     pub fn resources(&self) -> ResourcesUriBuilder<'_, Self> {
@@ -165,7 +165,7 @@ where
             .id
             .is_some()
             .then(|| self.build_join("pars"))
-            .unwrap_or(Err(UriBuildError::Validation.into()))
+            .unwrap_or(Err(UriBuildError::Validation))
     }
 
     /// Continue the builder into a
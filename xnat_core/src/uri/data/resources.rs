@@ -19,3 +19,14 @@ where
     #[parent]
     parent: Option<&'a Parent>
 }
+
+impl<Parent> ResourcesUriBuilder<'_, Parent>
+where
+    Parent: Display + Debug,
+{
+    /// Produce the URI path listing the files of
+    /// a resource.
+    pub fn files(&self) -> crate::BuildResult {
+        self.build_join_if("files", |this| this.resource.is_some() && this.file.is_none())
+    }
+}
@@ -2,7 +2,7 @@ use std::fmt::Display;
 
 /// Represents the URI paths available for a
 /// specific version of the XNAT REST API.
-pub trait Version: Display {
+pub trait Version: Display + Send + Sync {
     /// Represents the root URI used by the API
     /// version.
     fn root_uri(&self) -> String;